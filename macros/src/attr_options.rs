@@ -30,6 +30,19 @@ pub(crate) struct AttrOptions {
     /// `IntoIterator` from which to provide runtime arguments.
     pub args_expr: Option<Expr>,
 
+    /// Whether runtime arguments are passed through `black_box`.
+    pub black_box_args: bool,
+
+    /// Counter types for which runtime arguments are also used as counts.
+    pub args_counters: Vec<proc_macro2::TokenStream>,
+
+    /// Whether each iteration is expected to panic.
+    pub should_panic: bool,
+
+    /// Whether the returned value is passed through `black_box` and deferred
+    /// drop. Defaults to `true`.
+    pub black_box_return: bool,
+
     /// Options for generic functions.
     pub generic: GenericOptions,
 
@@ -50,6 +63,12 @@ impl AttrOptions {
         let mut divan_crate = None::<syn::Path>;
         let mut name_expr = None::<Expr>;
         let mut args_expr = None::<Expr>;
+        let mut black_box_args = None::<syn::LitBool>;
+        let mut should_panic = None::<syn::LitBool>;
+        let mut black_box_return = None::<syn::LitBool>;
+        let mut args_as_bytes = None::<syn::LitBool>;
+        let mut args_as_chars = None::<syn::LitBool>;
+        let mut args_as_items = None::<syn::LitBool>;
         let mut bench_options = Vec::new();
 
         let mut counters = Vec::<(proc_macro2::TokenStream, Option<&str>)>::new();
@@ -88,6 +107,26 @@ impl AttrOptions {
                 };
             }
 
+            // Parses a `#[divan::bench]` boolean option that can be written
+            // without a value.
+            macro_rules! parse_bool_flag {
+                ($storage:expr) => {{
+                    if !matches!(target_macro, Macro::Bench { .. }) {
+                        return unsupported_error();
+                    }
+
+                    if $storage.is_some() {
+                        return repeat_error();
+                    }
+
+                    // If the option is missing `=`, use a `true` literal.
+                    $storage = Some(match meta.value() {
+                        Ok(value) => value.parse()?,
+                        Err(_) => syn::LitBool::new(true, meta.path.span()),
+                    });
+                }};
+            }
+
             match ident_name {
                 "crate" => parse!(divan_crate),
                 "name" => parse!(name_expr),
@@ -127,6 +166,12 @@ impl AttrOptions {
 
                     parse!(args_expr);
                 }
+                "black_box_args" => parse_bool_flag!(black_box_args),
+                "args_as_bytes" => parse_bool_flag!(args_as_bytes),
+                "args_as_chars" => parse_bool_flag!(args_as_chars),
+                "args_as_items" => parse_bool_flag!(args_as_items),
+                "should_panic" => parse_bool_flag!(should_panic),
+                "black_box_return" => parse_bool_flag!(black_box_return),
                 "counter" => {
                     if counters_ident.is_some() {
                         return repeat_error();
@@ -195,10 +240,73 @@ impl AttrOptions {
             Err(error) => return Err(error.into_compile_error().into()),
         }
 
+        // An explicit `false` has no effect, so it is allowed without `args`.
+        if let (Some(black_box_args), None) =
+            (black_box_args.as_ref().filter(|lit| lit.value), &args_expr)
+        {
+            let message = format!("'{macro_name}' option 'black_box_args' requires 'args'");
+            return Err(syn::Error::new(black_box_args.span(), message)
+                .into_compile_error()
+                .into());
+        }
+        let black_box_args = black_box_args.is_some_and(|lit| lit.value);
+
+        let args_counters = [
+            ("BytesCount", args_as_bytes),
+            ("CharsCount", args_as_chars),
+            ("ItemsCount", args_as_items),
+        ];
+
+        if let (Some(args_counter), None) = (
+            args_counters.iter().find_map(|(_, value)| value.as_ref().filter(|lit| lit.value)),
+            &args_expr,
+        ) {
+            let message = format!("'{macro_name}' options 'args_as_*' require 'args'");
+            return Err(syn::Error::new(args_counter.span(), message).into_compile_error().into());
+        }
+
+        // Panics within `Bencher` closures cannot be caught separately from the
+        // benchmarking loop. `Bencher` is the first argument if there are more
+        // arguments than provided by `args`.
+        if let (Some(should_panic), Macro::Bench { fn_sig }) = (&should_panic, &target_macro) {
+            if fn_sig.inputs.len() > usize::from(args_expr.is_some()) {
+                let message =
+                    format!("'{macro_name}' option 'should_panic' is not supported with 'Bencher'");
+                return Err(syn::Error::new(should_panic.span(), message)
+                    .into_compile_error()
+                    .into());
+            }
+        }
+        let should_panic = should_panic.is_some_and(|lit| lit.value);
+
+        // The return value of `Bencher` closures is not handled by the macro.
+        if let (Some(black_box_return), Macro::Bench { fn_sig }) =
+            (&black_box_return, &target_macro)
+        {
+            if fn_sig.inputs.len() > usize::from(args_expr.is_some()) {
+                let message = format!(
+                    "'{macro_name}' option 'black_box_return' is not supported with 'Bencher'"
+                );
+                return Err(syn::Error::new(black_box_return.span(), message)
+                    .into_compile_error()
+                    .into());
+            }
+        }
+        let black_box_return = black_box_return.is_none_or(|lit| lit.value);
+
         let divan_crate = divan_crate.unwrap_or_else(|| syn::parse_quote!(::divan));
         let private_mod = quote! { #divan_crate::__private };
         let std_crate = quote! { #private_mod::std };
 
+        let args_counters = args_counters
+            .iter()
+            .filter(|(_, value)| value.as_ref().is_some_and(|lit| lit.value))
+            .map(|(type_name, _)| {
+                let type_name = Ident::new(type_name, proc_macro2::Span::call_site());
+                quote! { #divan_crate::counter::#type_name }
+            })
+            .collect();
+
         let counters = counters.iter().map(|(expr, type_name)| match type_name {
             Some(type_name) => {
                 let type_name = Ident::new(type_name, proc_macro2::Span::call_site());
@@ -223,7 +331,19 @@ impl AttrOptions {
             })
             .unwrap_or_default();
 
-        Ok(Self { std_crate, private_mod, name_expr, args_expr, generic, counters, bench_options })
+        Ok(Self {
+            std_crate,
+            private_mod,
+            name_expr,
+            args_expr,
+            black_box_args,
+            args_counters,
+            should_panic,
+            black_box_return,
+            generic,
+            counters,
+            bench_options,
+        })
     }
 
     /// Produces a function expression for creating `BenchOptions`.
@@ -277,6 +397,18 @@ impl AttrOptions {
                         &wrapped_value
                     }
 
+                    // Tags are either an array of string literals or a
+                    // `&'static [&'static str]`.
+                    "tags" => {
+                        wrapped_value = if is_lit_array(value) {
+                            quote! { #private_mod::Cow::Borrowed(&#value) }
+                        } else {
+                            quote! { #private_mod::Cow::Borrowed(#value) }
+                        };
+
+                        &wrapped_value
+                    }
+
                     // If the option is a `Duration`, use `IntoDuration` to be
                     // polymorphic over `Duration` or `u64`/`f64` seconds.
                     "min_time" | "max_time" => {
@@ -203,6 +203,37 @@ pub fn bench(options: TokenStream, item: TokenStream) -> TokenStream {
         })
         .unwrap_or_default();
 
+    // The runtime argument passed to the benchmarked function.
+    let arg_value = {
+        let arg_value = quote! {
+            #private_mod::Arg::<#last_arg_type_tokens>::get(__divan_arg)
+        };
+
+        if options.black_box_args {
+            quote! { #std_crate::hint::black_box(#arg_value) }
+        } else {
+            arg_value
+        }
+    };
+
+    // Counters of the runtime argument, applied to `Bencher` before the
+    // benchmarked function is called.
+    let arg_counters: proc_macro2::TokenStream = options
+        .args_counters
+        .iter()
+        .map(|counter| {
+            quote! {
+                .counter({
+                    // We do a scoped import for the expression to override any
+                    // local `From` trait.
+                    use #std_crate::convert::From as _;
+
+                    #counter::from(#private_mod::Arg::<#last_arg_type_tokens>::get(__divan_arg))
+                })
+            }
+        })
+        .collect();
+
     // Creates a function expr for the benchmarking function, optionally
     // monomorphized with generic parameters.
     let make_bench_fn = |generics: &[&dyn ToTokens]| {
@@ -223,6 +254,16 @@ pub fn bench(options: TokenStream, item: TokenStream) -> TokenStream {
                     fn_expr = quote! { || #fn_expr() };
                 }
 
+                // Drop the returned value within the iteration.
+                if !options.black_box_return {
+                    fn_expr = quote! { || { let _ = (#fn_expr)(); } };
+                }
+
+                // Catch the expected panic of each iteration.
+                if options.should_panic {
+                    fn_expr = quote! { || #private_mod::expect_panic(#fn_expr) };
+                }
+
                 quote! {
                     #bench_entry_runner::Plain(|divan /* Bencher */| divan.bench(#fn_expr))
                 }
@@ -243,17 +284,29 @@ pub fn bench(options: TokenStream, item: TokenStream) -> TokenStream {
             }
 
             // Function argument comes from `args` option.
-            (1, Some(args)) => quote! {
-                #bench_entry_runner::Args(|| __DIVAN_ARGS.runner(
-                    || #arg_return_tokens { #args },
+            (1, Some(args)) => {
+                let mut benched = if options.black_box_return {
+                    quote! { || #fn_expr(#arg_value) }
+                } else {
+                    // Drop the returned value within the iteration.
+                    quote! { || { let _ = #fn_expr(#arg_value); } }
+                };
 
-                    |arg| #private_mod::ToStringHelper(arg).to_string(),
+                // Catch the expected panic of each iteration.
+                if options.should_panic {
+                    benched = quote! { || #private_mod::expect_panic(#benched) };
+                }
 
-                    |divan, __divan_arg| divan.bench(|| #fn_expr(
-                        #private_mod::Arg::<#last_arg_type_tokens>::get(__divan_arg)
-                    )),
-                ))
-            },
+                quote! {
+                    #bench_entry_runner::Args(|| __DIVAN_ARGS.runner(
+                        || #arg_return_tokens { #args },
+
+                        |arg| #private_mod::ToStringHelper(arg).to_string(),
+
+                        |divan, __divan_arg| divan #arg_counters .bench(#benched),
+                    ))
+                }
+            }
 
             // `Bencher` and `args` option function arguments.
             (2, Some(args)) => quote! {
@@ -262,10 +315,7 @@ pub fn bench(options: TokenStream, item: TokenStream) -> TokenStream {
 
                     |arg| #private_mod::ToStringHelper(arg).to_string(),
 
-                    |divan, __divan_arg| #fn_expr(
-                        divan,
-                        #private_mod::Arg::<#last_arg_type_tokens>::get(__divan_arg),
-                    ),
+                    |divan, __divan_arg| #fn_expr(divan #arg_counters, #arg_value),
                 ))
             },
 
@@ -1,10 +1,11 @@
 use std::{
     cell::UnsafeCell,
     fmt,
-    mem::{self, MaybeUninit},
+    mem::{self, ManuallyDrop, MaybeUninit},
     num::NonZeroUsize,
-    sync::Barrier,
+    sync::{Barrier, Mutex, PoisonError},
     thread,
+    time::Instant,
 };
 
 use crate::{
@@ -18,13 +19,13 @@ use crate::{
         ItemsCount, KnownCounterKind, MaxCountUInt,
     },
     divan::SharedContext,
-    stats::{RawSample, SampleCollection, Stats, StatsSet, ThreadSample, TimeSample},
+    stats::{RawSample, SampleCollection, SampleStats, Stats, StatsSet, ThreadSample, TimeSample},
     time::{FineDuration, Timestamp, UntaggedTimestamp},
     util::{self, SyncWrap, Unit},
 };
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
 mod args;
 mod defer;
@@ -67,8 +68,9 @@ pub struct Bencher<'a, 'b, C = BencherConfig> {
 ///
 /// This enables configuring `Bencher` using the builder pattern with zero
 /// runtime cost.
-pub struct BencherConfig<GenI = Unit> {
+pub struct BencherConfig<GenI = Unit, Td = Unit> {
     gen_input: GenI,
+    teardown: Td,
 }
 
 impl<C> fmt::Debug for Bencher<'_, '_, C> {
@@ -80,7 +82,7 @@ impl<C> fmt::Debug for Bencher<'_, '_, C> {
 impl<'a, 'b> Bencher<'a, 'b> {
     #[inline]
     pub(crate) fn new(context: &'a mut BenchContext<'b>) -> Self {
-        Self { context, config: BencherConfig { gen_input: Unit } }
+        Self { context, config: BencherConfig { gen_input: Unit, teardown: Unit } }
     }
 }
 
@@ -105,9 +107,43 @@ impl<'a, 'b> Bencher<'a, 'b> {
     where
         B: Fn() -> O + Sync,
     {
-        // Reusing `bench_values` for a zero-sized non-drop input type should
-        // have no overhead.
-        self.with_inputs(|| ()).bench_values(|_: ()| benched());
+        // A zero-sized non-drop input type should have no overhead. This does
+        // not go through `bench_values` because there is no setup to measure.
+        self.context.bench_loop_threaded(|| (), |_| benched(), |_| {});
+    }
+
+    /// Benchmarks a function like [`Bencher::bench`] and returns statistics of
+    /// its samples.
+    ///
+    /// This enables a benchmark to programmatically use its own timing, such as
+    /// to assert on it or log it. The returned statistics reflect the same
+    /// samples that Divan records and prints for this benchmark.
+    ///
+    /// Returns [`None`] if no samples were recorded, such as when benchmarks
+    /// are run once as tests via `--test`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[divan::bench]
+    /// fn bench(bencher: divan::Bencher) {
+    ///     let stats = bencher.bench_measured(|| {
+    ///         // Benchmarked code...
+    ///     });
+    ///
+    ///     if let Some(stats) = stats {
+    ///         assert!(stats.median < Duration::from_secs(1));
+    ///     }
+    /// }
+    /// ```
+    pub fn bench_measured<O, B>(self, benched: B) -> Option<SampleStats>
+    where
+        B: Fn() -> O + Sync,
+    {
+        self.context.bench_loop_threaded(|| (), |_| benched(), |_| {});
+        self.context.sample_stats()
     }
 
     /// Benchmarks a function on the current thread.
@@ -126,14 +162,17 @@ impl<'a, 'b> Bencher<'a, 'b> {
     where
         B: FnMut() -> O,
     {
-        // Reusing `bench_local_values` for a zero-sized non-drop input type
-        // should have no overhead.
-        self.with_inputs(|| ()).bench_local_values(|_: ()| benched());
+        // A zero-sized non-drop input type should have no overhead. This does
+        // not go through `bench_local_values` because there is no setup to
+        // measure.
+        self.context.bench_loop_local(|| (), |_| benched(), |_| {});
     }
 
     /// Generate inputs for the [benchmarked function](#input-bench).
     ///
-    /// Time spent generating inputs does not affect benchmark timing.
+    /// Time spent generating inputs does not affect benchmark timing. It can
+    /// instead be reported separately via `--measure-setup` or
+    /// [`Divan::measure_setup`](crate::Divan::measure_setup).
     ///
     /// When [benchmarking in parallel](macro@crate::bench#threads), the input
     /// generator is called on the same thread as the sample loop that uses that
@@ -156,11 +195,166 @@ impl<'a, 'b> Bencher<'a, 'b> {
     /// }
     /// ```
     pub fn with_inputs<G>(self, gen_input: G) -> Bencher<'a, 'b, BencherConfig<G>> {
-        Bencher { context: self.context, config: BencherConfig { gen_input } }
+        Bencher { context: self.context, config: BencherConfig { gen_input, teardown: Unit } }
+    }
+
+    /// Skips the benchmark without running it, such as when required hardware
+    /// or input data is unavailable.
+    ///
+    /// The benchmark is reported as `(skipped)` rather than with statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[divan::bench]
+    /// fn bench(bencher: divan::Bencher) {
+    ///     let Ok(data) = std::fs::read("data.bin") else {
+    ///         return bencher.skip();
+    ///     };
+    ///
+    ///     bencher.bench(|| {
+    ///         divan::black_box(&data).len()
+    ///     });
+    /// }
+    /// ```
+    pub fn skip(self) {
+        self.context.skipped = true;
+    }
+
+    /// Benchmarks a function after checking that it returns `expected`.
+    ///
+    /// This guards against benchmarks that are fast because they compute the
+    /// wrong result, such as from a bug or from work being optimized away.
+    ///
+    /// `f` is first called once untimed, separately from and before any
+    /// samples are taken. If its result does not equal `expected`, the
+    /// benchmark fails with a panic that shows both values. Otherwise, `f` is
+    /// then benchmarked like with [`Bencher::bench_local`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[divan::bench]
+    /// fn sum(bencher: divan::Bencher) {
+    ///     let items: Vec<u64> = (1..=100).collect();
+    ///
+    ///     bencher.verify(5050, || {
+    ///         divan::black_box(&items).iter().sum::<u64>()
+    ///     });
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn verify<T, F>(self, expected: T, mut f: F)
+    where
+        T: PartialEq + fmt::Debug,
+        F: FnMut() -> T,
+    {
+        let actual = f();
+
+        assert!(
+            actual == expected,
+            "benchmark result does not match expected value\n  expected: {expected:?}\n    actual: {actual:?}",
+        );
+
+        self.bench_local(f);
+    }
+}
+
+impl<'a, 'b, I, GenI> Bencher<'a, 'b, BencherConfig<GenI>>
+where
+    GenI: FnMut() -> I,
+{
+    /// Releases each output of the [benchmarked function](#input-bench) with
+    /// `teardown`, which is timed separately from the benchmarked function.
+    ///
+    /// This is useful for operations that acquire resources needing explicit
+    /// release each iteration, such as freeing a GPU buffer, where the release
+    /// should be measured rather than folded into or excluded from the
+    /// benchmark. Teardown time is reported in a `teardown` row under the
+    /// benchmark.
+    ///
+    /// Teardown takes the place of the deferred drop of outputs. For each
+    /// sample, all inputs are generated, then the benchmarked function is timed
+    /// over every input, then `teardown` is timed over every output in the same
+    /// order, and finally inputs are dropped outside of either timed section.
+    /// Outputs are dropped within `teardown`, so their drop time is included
+    /// in teardown time.
+    ///
+    /// When [benchmarking in parallel](macro@crate::bench#threads), `teardown`
+    /// is called on the same thread as the sample loop that produced the
+    /// output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[divan::bench]
+    /// fn bench(bencher: divan::Bencher) {
+    ///     bencher
+    ///         .with_inputs(|| 1024)
+    ///         .with_teardown(|buf: Vec<u8>| {
+    ///             // Release output:
+    ///             drop(buf);
+    ///         })
+    ///         .bench_values(|len| {
+    ///             // Acquire output:
+    ///             vec![0u8; len]
+    ///         });
+    /// }
+    /// ```
+    pub fn with_teardown<O, Td>(self, teardown: Td) -> Bencher<'a, 'b, BencherConfig<GenI, Td>>
+    where
+        Td: Fn(O),
+    {
+        let gen_input = self.config.gen_input;
+        Bencher { context: self.context, config: BencherConfig { gen_input, teardown } }
+    }
+
+    /// Passes each output of the [benchmarked function](#input-bench) to
+    /// `consume` outside of timing, such as to accumulate or checksum results.
+    ///
+    /// Unlike [`Bencher::with_teardown`], `consume` is not timed. It takes the
+    /// place of the deferred drop of outputs: once each sample is timed,
+    /// `consume` is called with every output of that sample in order. Since
+    /// outputs are observed by `consume`, the optimizer cannot remove the work
+    /// that produced them.
+    ///
+    /// Outputs are held until the end of their sample, as when they are
+    /// dropped, so `consume` does not bound memory usage: every output of a
+    /// sample is alive at once. Large outputs can be limited by setting a
+    /// small [`sample_size`](macro@crate::bench#sample_size).
+    ///
+    /// `consume` can mutate state, such as pushing to a captured [`Vec`]. When
+    /// [benchmarking in parallel](macro@crate::bench#threads), calls are
+    /// serialized across threads, so `consume` must be [`Send`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[divan::bench]
+    /// fn bench(bencher: divan::Bencher) {
+    ///     let mut checksum = 0;
+    ///
+    ///     bencher
+    ///         .with_inputs(|| 1024)
+    ///         .with_consume(|buf: Vec<u8>| {
+    ///             checksum += buf.len();
+    ///         })
+    ///         .bench_values(|len| vec![0u8; len]);
+    /// }
+    /// ```
+    pub fn with_consume<O, C>(self, consume: C) -> Bencher<'a, 'b, BencherConfig<GenI, Consume<C>>>
+    where
+        C: FnMut(O),
+    {
+        let gen_input = self.config.gen_input;
+        Bencher {
+            context: self.context,
+            config: BencherConfig { gen_input, teardown: Consume(Mutex::new(consume)) },
+        }
     }
 }
 
-impl<'a, 'b, GenI> Bencher<'a, 'b, BencherConfig<GenI>> {
+impl<'a, 'b, GenI, Td> Bencher<'a, 'b, BencherConfig<GenI, Td>> {
     /// Assign a [`Counter`] for all iterations of the benchmarked function.
     ///
     /// This will either:
@@ -202,7 +396,7 @@ impl<'a, 'b, GenI> Bencher<'a, 'b, BencherConfig<GenI>> {
 }
 
 /// <span id="input-bench"></span> Benchmark over [generated inputs](Self::with_inputs).
-impl<'a, 'b, I, GenI> Bencher<'a, 'b, BencherConfig<GenI>>
+impl<'a, 'b, I, GenI, Td> Bencher<'a, 'b, BencherConfig<GenI, Td>>
 where
     GenI: FnMut() -> I,
 {
@@ -297,6 +491,12 @@ where
     /// Per-iteration means the benchmarked function is called exactly once for
     /// each generated input.
     ///
+    /// Ownership of each input is transferred to the benchmarked function. All
+    /// inputs for a sample are generated before it is timed, and returned
+    /// outputs are dropped after it is timed. However, inputs dropped within
+    /// the benchmarked function are dropped in the timed section. To exclude
+    /// dropping from timing, return the input or any value that owns it.
+    ///
     /// The function can be benchmarked in parallel using the [`threads`
     /// option](macro@crate::bench#threads). If the function is strictly
     /// single-threaded, use [`Bencher::bench_local_values`] instead.
@@ -321,19 +521,25 @@ where
     where
         B: Fn(I) -> O + Sync,
         GenI: Fn() -> I + Sync,
+        Td: Teardown<O> + Sync,
     {
+        let teardown = &self.config.teardown;
+        self.context.has_teardown = Td::IS_SET;
+
         self.context.bench_loop_threaded(
-            self.config.gen_input,
+            &self.config.gen_input,
             |input| {
                 // SAFETY: Input is guaranteed to be initialized and not
                 // currently referenced by anything else.
                 let input = unsafe { input.get().read().assume_init() };
 
-                benched(input)
+                teardown.defer(benched(input))
             },
             // Input ownership is transferred to `benched`.
             |_input| {},
         );
+
+        self.context.measure_setup(&self.config.gen_input);
     }
 
     /// Benchmarks a function over per-iteration [generated inputs](Self::with_inputs),
@@ -342,6 +548,8 @@ where
     /// Per-iteration means the benchmarked function is called exactly once for
     /// each generated input.
     ///
+    /// See [`Bencher::bench_values`] for when inputs are generated and dropped.
+    ///
     /// # Examples
     ///
     /// ```
@@ -362,19 +570,26 @@ where
     pub fn bench_local_values<O, B>(self, mut benched: B)
     where
         B: FnMut(I) -> O,
+        Td: Teardown<O>,
     {
+        let mut gen_input = self.config.gen_input;
+        let teardown = &self.config.teardown;
+        self.context.has_teardown = Td::IS_SET;
+
         self.context.bench_loop_local(
-            self.config.gen_input,
+            &mut gen_input,
             |input| {
                 // SAFETY: Input is guaranteed to be initialized and not
                 // currently referenced by anything else.
                 let input = unsafe { input.get().read().assume_init() };
 
-                benched(input)
+                teardown.defer(benched(input))
             },
             // Input ownership is transferred to `benched`.
             |_input| {},
         );
+
+        self.context.measure_setup(gen_input);
     }
 
     /// Benchmarks a function over per-iteration [generated inputs](Self::with_inputs),
@@ -403,16 +618,20 @@ where
     where
         B: Fn(&mut I) -> O + Sync,
         GenI: Fn() -> I + Sync,
+        Td: Teardown<O> + Sync,
     {
+        let teardown = &self.config.teardown;
+        self.context.has_teardown = Td::IS_SET;
+
         // TODO: Allow `O` to reference `&mut I` as long as `I` outlives `O`.
         self.context.bench_loop_threaded(
-            self.config.gen_input,
+            &self.config.gen_input,
             |input| {
                 // SAFETY: Input is guaranteed to be initialized and not
                 // currently referenced by anything else.
                 let input = unsafe { (*input.get()).assume_init_mut() };
 
-                benched(input)
+                teardown.defer(benched(input))
             },
             // Input ownership was not transferred to `benched`.
             |input| {
@@ -421,6 +640,8 @@ where
                 unsafe { (*input.get()).assume_init_drop() }
             },
         );
+
+        self.context.measure_setup(&self.config.gen_input);
     }
 
     /// Benchmarks a function over per-iteration [generated inputs](Self::with_inputs),
@@ -448,16 +669,21 @@ where
     pub fn bench_local_refs<O, B>(self, mut benched: B)
     where
         B: FnMut(&mut I) -> O,
+        Td: Teardown<O>,
     {
         // TODO: Allow `O` to reference `&mut I` as long as `I` outlives `O`.
+        let mut gen_input = self.config.gen_input;
+        let teardown = &self.config.teardown;
+        self.context.has_teardown = Td::IS_SET;
+
         self.context.bench_loop_local(
-            self.config.gen_input,
+            &mut gen_input,
             |input| {
                 // SAFETY: Input is guaranteed to be initialized and not
                 // currently referenced by anything else.
                 let input = unsafe { (*input.get()).assume_init_mut() };
 
-                benched(input)
+                teardown.defer(benched(input))
             },
             // Input ownership was not transferred to `benched`.
             |input| {
@@ -466,6 +692,102 @@ where
                 unsafe { (*input.get()).assume_init_drop() }
             },
         );
+
+        self.context.measure_setup(gen_input);
+    }
+}
+
+/// Public-in-private trait for releasing outputs of the benchmarked function,
+/// set via [`Bencher::with_teardown`] or [`Bencher::with_consume`].
+pub trait Teardown<O> {
+    /// Whether teardown was set and should be timed.
+    const IS_SET: bool;
+
+    /// The output stored until after the sample is timed.
+    type Deferred<'t>
+    where
+        Self: 't;
+
+    /// Wraps `output` to be released when dropped.
+    fn defer(&self, output: O) -> Self::Deferred<'_>;
+}
+
+/// No teardown: outputs are simply dropped.
+impl<O> Teardown<O> for Unit {
+    const IS_SET: bool = false;
+
+    type Deferred<'t> = O;
+
+    #[inline(always)]
+    fn defer(&self, output: O) -> O {
+        output
+    }
+}
+
+impl<O, F: Fn(O)> Teardown<O> for F {
+    const IS_SET: bool = true;
+
+    type Deferred<'t>
+        = TeardownOnDrop<'t, O, F>
+    where
+        Self: 't;
+
+    #[inline(always)]
+    fn defer(&self, output: O) -> TeardownOnDrop<'_, O, F> {
+        TeardownOnDrop { output: ManuallyDrop::new(output), teardown: self }
+    }
+}
+
+/// Output that calls its teardown when dropped.
+pub struct TeardownOnDrop<'t, O, F: Fn(O)> {
+    output: ManuallyDrop<O>,
+    teardown: &'t F,
+}
+
+impl<O, F: Fn(O)> Drop for TeardownOnDrop<'_, O, F> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: `output` is not used after being taken.
+        let output = unsafe { ManuallyDrop::take(&mut self.output) };
+        (self.teardown)(output);
+    }
+}
+
+/// Public-in-private wrapper for an untimed function that releases outputs,
+/// set via [`Bencher::with_consume`].
+///
+/// The function is behind a [`Mutex`] so that it can be [`FnMut`] while
+/// outputs are deferred by shared reference, including across threads.
+pub struct Consume<F>(Mutex<F>);
+
+impl<O, F: FnMut(O)> Teardown<O> for Consume<F> {
+    // Outputs are consumed where they would otherwise be dropped, outside of
+    // timing.
+    const IS_SET: bool = false;
+
+    type Deferred<'t>
+        = ConsumeOnDrop<'t, O, F>
+    where
+        Self: 't;
+
+    #[inline(always)]
+    fn defer(&self, output: O) -> ConsumeOnDrop<'_, O, F> {
+        ConsumeOnDrop { output: ManuallyDrop::new(output), consume: &self.0 }
+    }
+}
+
+/// Output that is passed to its consume function when dropped.
+pub struct ConsumeOnDrop<'c, O, F: FnMut(O)> {
+    output: ManuallyDrop<O>,
+    consume: &'c Mutex<F>,
+}
+
+impl<O, F: FnMut(O)> Drop for ConsumeOnDrop<'_, O, F> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: `output` is not used after being taken.
+        let output = unsafe { ManuallyDrop::take(&mut self.output) };
+        (self.consume.lock().unwrap_or_else(PoisonError::into_inner))(output);
     }
 }
 
@@ -522,6 +844,13 @@ pub(crate) struct BenchContext<'a> {
     /// Whether the benchmark loop was started.
     pub did_run: bool,
 
+    /// Whether the benchmark was skipped via `Bencher::skip`.
+    pub skipped: bool,
+
+    /// Whether sampling stopped at `max_time` before collecting the expected
+    /// number of samples.
+    pub hit_max_time: bool,
+
     /// The number of threads to run the benchmark. The default is 1.
     ///
     /// When set to 1, the benchmark loop is guaranteed to stay on the current
@@ -533,6 +862,44 @@ pub(crate) struct BenchContext<'a> {
 
     /// Per-iteration counters grouped by sample.
     counters: CounterCollection,
+
+    /// Samples of only generating inputs, collected via `--measure-setup`.
+    setup_samples: SampleCollection,
+
+    /// Whether outputs are released by a teardown set via
+    /// `Bencher::with_teardown`.
+    pub has_teardown: bool,
+
+    /// Samples of only releasing outputs via `Bencher::with_teardown`.
+    teardown_samples: SampleCollection,
+
+    /// Wall-clock time spent in each phase of benchmarking.
+    pub phase_times: PhaseTimes,
+
+    /// Time of the first call before sampling, measured via `--show-cold`.
+    cold_time: Option<FineDuration>,
+}
+
+/// Wall-clock time spent in each phase of benchmarking, reported by
+/// `--time-breakdown`.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct PhaseTimes {
+    /// Time spent tuning the sample size.
+    pub warmup: FineDuration,
+
+    /// Time spent collecting samples, including discarded ones.
+    pub sampling: FineDuration,
+
+    /// Time spent timing input generation via `--measure-setup`.
+    pub setup: FineDuration,
+}
+
+impl std::ops::AddAssign for PhaseTimes {
+    fn add_assign(&mut self, other: Self) {
+        self.warmup += other.warmup;
+        self.sampling += other.sampling;
+        self.setup += other.setup;
+    }
 }
 
 impl<'a> BenchContext<'a> {
@@ -547,8 +914,15 @@ impl<'a> BenchContext<'a> {
             options,
             thread_count,
             did_run: false,
+            skipped: false,
+            hit_max_time: false,
             samples: SampleCollection::default(),
             counters: options.counters.to_collection(),
+            setup_samples: SampleCollection::default(),
+            has_teardown: false,
+            teardown_samples: SampleCollection::default(),
+            phase_times: PhaseTimes::default(),
+            cold_time: None,
         }
     }
 
@@ -626,14 +1000,21 @@ impl<'a> BenchContext<'a> {
         // `benched`, such as time spent generating inputs and running drop.
         let mut elapsed_picos: u128 = 0;
 
+        // Time limits make the number of samples depend on machine speed.
+        let is_deterministic = self.shared_context.deterministic;
+
         // The minimum time for benchmarking, in picoseconds.
-        let min_picos = self.options.min_time().picos;
+        let min_picos = if is_deterministic { 0 } else { self.options.min_time().picos };
 
         // The remaining time left for benchmarking, in picoseconds.
-        let max_picos = self.options.max_time().picos;
+        let max_picos =
+            if is_deterministic { FineDuration::MAX.picos } else { self.options.max_time().picos };
+
+        // The number of samples to collect regardless of the time budget.
+        let min_samples = self.options.min_samples.unwrap_or_default() as usize;
 
         // Don't bother running if user specifies 0 max time or 0 samples.
-        if max_picos == 0 || !self.options.has_samples() {
+        if (max_picos == 0 && min_samples == 0) || !self.options.has_samples() {
             return;
         }
 
@@ -654,12 +1035,36 @@ impl<'a> BenchContext<'a> {
             self.samples.time_samples.reserve(self.options.sample_count.unwrap_or(1) as usize);
         }
 
+        // The remaining number of collected sample sets to discard.
+        let mut rem_drop = self.options.drop_first.unwrap_or_default();
+
+        // Time the first call on the current thread before any tuning or
+        // sampling warms up caches and lazily-initialized state.
+        if self.shared_context.measure_cold && !is_test {
+            let ([start, end], ..) = record_sample(1, None, &mut defer_store, &mut |_| {});
+
+            let overhead = self.shared_context.bench_overhead;
+            self.cold_time = Some(FineDuration {
+                picos: end.duration_since(start, timer).picos.saturating_sub(overhead.picos),
+            });
+        }
+
         let skip_ext_time = self.options.skip_ext_time.unwrap_or_default();
         let initial_start = if skip_ext_time { None } else { Some(Timestamp::start(timer_kind)) };
 
+        // Start of the current loop iteration, for `--time-breakdown`.
+        let mut phase_start = Instant::now();
+
         while {
             // Conditions for when sampling is over:
-            if elapsed_picos >= max_picos {
+            if crate::interrupt::is_interrupted() {
+                // Stop sampling as soon as the user interrupts the run.
+                false
+            } else if self.samples.time_samples.len() < min_samples {
+                // Collect the minimum number of samples regardless of the time
+                // budget. Tuning samples are cleared, so they don't count.
+                true
+            } else if elapsed_picos >= max_picos {
                 // Depleted the benchmarking time budget. This is a strict
                 // condition regardless of sample count and minimum time.
                 false
@@ -671,8 +1076,10 @@ impl<'a> BenchContext<'a> {
                 elapsed_picos < min_picos
             }
         } {
+            let was_tuning = current_mode.is_tune();
             let sample_size = current_mode.sample_size();
             self.samples.sample_size = sample_size;
+            self.teardown_samples.sample_size = sample_size;
 
             let barrier = if is_single_thread { None } else { Some(Barrier::new(thread_count)) };
 
@@ -696,21 +1103,26 @@ impl<'a> BenchContext<'a> {
                 };
 
                 // Sample loop:
-                let ([start, end], alloc_tallies) = record_sample(
+                let ([start, end], teardown_interval, alloc_tallies) = record_sample(
                     sample_size as usize,
                     barrier.as_ref(),
                     defer_store,
                     &mut count_input,
                 );
 
-                RawSample { start, end, timer, alloc_tallies, counter_totals }
+                let teardown_time =
+                    teardown_interval.map(|[start, end]| end.duration_since(start, timer));
+
+                RawSample { start, end, timer, alloc_tallies, counter_totals, teardown_time }
             };
 
             // Sample loop:
             raw_samples.clear();
             if is_single_thread {
                 let sample = record_sample(&mut defer_store);
-                if !is_test {
+                if is_test {
+                    self.samples.insert_test_alloc_tallies(sample.alloc_tallies);
+                } else {
                     raw_samples.push(sample);
                 }
             } else {
@@ -722,7 +1134,9 @@ impl<'a> BenchContext<'a> {
 
                     let local_sample = record_sample(&mut defer_store);
 
-                    if !is_test {
+                    if is_test {
+                        self.samples.insert_test_alloc_tallies(local_sample.alloc_tallies);
+                    } else {
                         raw_samples.extend(
                             thread_handles
                                 .into_iter()
@@ -767,6 +1181,7 @@ impl<'a> BenchContext<'a> {
             if current_mode.is_tune() {
                 // Clear previous smaller samples.
                 self.samples.clear();
+                self.teardown_samples.clear();
                 self.counters.clear_input_counts();
 
                 // If within 100x timer precision, continue tuning.
@@ -793,48 +1208,66 @@ impl<'a> BenchContext<'a> {
                 }
             };
 
-            if is_multi_thread {
-                // The total wall clock time spent over the current
-                // multi-threaded sample set.
-                let total_wall_time = {
-                    let first_start = raw_samples.iter().map(|s| s.start).min().unwrap();
-                    let last_end = raw_samples.iter().map(|s| s.end).max().unwrap();
-                    sub_sample_overhead(last_end.duration_since(first_start, timer))
-                };
+            // Discard the first collected samples, but always keep at least one
+            // sample set for computing statistics.
+            let should_drop = rem_drop > 0
+                && current_mode.is_collect()
+                && rem_samples.is_some_and(|rem| rem as usize > raw_samples.len());
 
-                self.samples.threads.push(ThreadSample { total_wall_time });
-            }
+            if should_drop {
+                rem_drop -= 1;
 
-            for raw_sample in &raw_samples {
-                let sample_index = self.samples.time_samples.len();
+                if let Some(rem_samples) = &mut rem_samples {
+                    *rem_samples = rem_samples.saturating_sub(raw_samples.len() as u32);
+                }
+            } else {
+                if is_multi_thread {
+                    // The total wall clock time spent over the current
+                    // multi-threaded sample set.
+                    let total_wall_time = {
+                        let first_start = raw_samples.iter().map(|s| s.start).min().unwrap();
+                        let last_end = raw_samples.iter().map(|s| s.end).max().unwrap();
+                        sub_sample_overhead(last_end.duration_since(first_start, timer))
+                    };
+
+                    self.samples.threads.push(ThreadSample { total_wall_time });
+                }
 
-                self.samples
-                    .time_samples
-                    .push(TimeSample { duration: sub_sample_overhead(raw_sample.duration()) });
+                for raw_sample in &raw_samples {
+                    let sample_index = self.samples.time_samples.len();
 
-                if !raw_sample.alloc_tallies.is_empty() {
                     self.samples
-                        .alloc_tallies
-                        .insert(sample_index as u32, raw_sample.alloc_tallies);
-                }
+                        .time_samples
+                        .push(TimeSample { duration: sub_sample_overhead(raw_sample.duration()) });
 
-                // Insert per-input counter information.
-                for counter_kind in KnownCounterKind::ALL {
-                    if !self.counters.uses_input_counts(counter_kind) {
-                        continue;
+                    if let Some(duration) = raw_sample.teardown_time {
+                        self.teardown_samples.time_samples.push(TimeSample { duration });
                     }
 
-                    let total_count = raw_sample.counter_totals[counter_kind as usize];
+                    if !raw_sample.alloc_tallies.is_empty() {
+                        self.samples
+                            .alloc_tallies
+                            .insert(sample_index as u32, raw_sample.alloc_tallies);
+                    }
 
-                    // Cannot overflow `MaxCountUInt` because `total_count`
-                    // cannot exceed `MaxCountUInt::MAX * sample_size`.
-                    let per_iter_count = (total_count / sample_size as u128) as MaxCountUInt;
+                    // Insert per-input counter information.
+                    for counter_kind in KnownCounterKind::ALL {
+                        if !self.counters.uses_input_counts(counter_kind) {
+                            continue;
+                        }
 
-                    self.counters.push_counter(AnyCounter::known(counter_kind, per_iter_count));
-                }
+                        let total_count = raw_sample.counter_totals[counter_kind as usize];
 
-                if let Some(rem_samples) = &mut rem_samples {
-                    *rem_samples = rem_samples.saturating_sub(1);
+                        // Cannot overflow `MaxCountUInt` because `total_count`
+                        // cannot exceed `MaxCountUInt::MAX * sample_size`.
+                        let per_iter_count = (total_count / sample_size as u128) as MaxCountUInt;
+
+                        self.counters.push_counter(AnyCounter::known(counter_kind, per_iter_count));
+                    }
+
+                    if let Some(rem_samples) = &mut rem_samples {
+                        *rem_samples = rem_samples.saturating_sub(1);
+                    }
                 }
             }
 
@@ -847,7 +1280,22 @@ impl<'a> BenchContext<'a> {
                 let progress_picos = slowest_time.picos.max(1_000);
                 elapsed_picos = elapsed_picos.saturating_add(progress_picos);
             }
+
+            let phase_end = Instant::now();
+            let phase_time = FineDuration::from(phase_end - phase_start);
+            phase_start = phase_end;
+
+            if was_tuning {
+                self.phase_times.warmup += phase_time;
+            } else {
+                self.phase_times.sampling += phase_time;
+            }
         }
+
+        self.hit_max_time = !is_test
+            && !crate::interrupt::is_interrupted()
+            && elapsed_picos >= max_picos
+            && rem_samples.unwrap_or(1) > 0;
     }
 
     /// Returns a closure that takes the sample size and input counter, and then
@@ -862,7 +1310,7 @@ impl<'a> BenchContext<'a> {
         Option<&Barrier>,
         &mut DeferStore<I, O>,
         &mut dyn FnMut(&I),
-    ) -> ([Timestamp; 2], ThreadAllocTallyMap) {
+    ) -> ([Timestamp; 2], Option<[Timestamp; 2]>, ThreadAllocTallyMap) {
         // We defer:
         // - Usage of `gen_input` values.
         // - Drop destructor for `O`, preventing it from affecting sample
@@ -872,6 +1320,9 @@ impl<'a> BenchContext<'a> {
 
         let timer_kind = self.shared_context.timer.kind();
 
+        // Teardown is timed in place of dropping outputs.
+        let has_teardown = self.has_teardown;
+
         move |sample_size: usize,
               barrier: Option<&Barrier>,
               defer_store: &mut DeferStore<I, O>,
@@ -935,6 +1386,7 @@ impl<'a> BenchContext<'a> {
             // benchmarking.
             let sample_start: UntaggedTimestamp;
             let sample_end: UntaggedTimestamp;
+            let mut teardown_interval: Option<[UntaggedTimestamp; 2]> = None;
 
             if mem::size_of::<I>() == 0 && (mem::size_of::<O>() == 0 || !mem::needs_drop::<O>()) {
                 // Use a range instead of `defer_store` to make the benchmarking
@@ -1025,16 +1477,39 @@ impl<'a> BenchContext<'a> {
                         // and outputs in the sample loop.
                         black_box(defer_slots_slice);
 
-                        // Drop outputs and inputs.
-                        for DeferSlot { input, output } in defer_slots_slice {
-                            // SAFETY: All outputs were initialized in the
-                            // sample loop and we have exclusive access.
-                            unsafe { (*output.get()).assume_init_drop() }
+                        if has_teardown {
+                            // Time teardown of outputs separately from
+                            // dropping inputs.
+                            let teardown_start = UntaggedTimestamp::start(timer_kind);
+
+                            for DeferSlot { output, .. } in defer_slots_slice {
+                                // SAFETY: All outputs were initialized in the
+                                // sample loop and we have exclusive access.
+                                unsafe { (*output.get()).assume_init_drop() }
+                            }
+
+                            let teardown_end = UntaggedTimestamp::end(timer_kind);
+                            teardown_interval = Some([teardown_start, teardown_end]);
 
                             if mem::needs_drop::<I>() {
-                                // SAFETY: The output was dropped and thus we
-                                // have exclusive access to inputs.
-                                unsafe { drop_input(input) }
+                                for DeferSlot { input, .. } in defer_slots_slice {
+                                    // SAFETY: All outputs were dropped and thus
+                                    // we have exclusive access to inputs.
+                                    unsafe { drop_input(input) }
+                                }
+                            }
+                        } else {
+                            // Drop outputs and inputs.
+                            for DeferSlot { input, output } in defer_slots_slice {
+                                // SAFETY: All outputs were initialized in the
+                                // sample loop and we have exclusive access.
+                                unsafe { (*output.get()).assume_init_drop() }
+
+                                if mem::needs_drop::<I>() {
+                                    // SAFETY: The output was dropped and thus
+                                    // we have exclusive access to inputs.
+                                    unsafe { drop_input(input) }
+                                }
                             }
                         }
                     }
@@ -1091,11 +1566,64 @@ impl<'a> BenchContext<'a> {
                 [sample_start.into_timestamp(timer_kind), sample_end.into_timestamp(timer_kind)]
             };
 
-            (interval, alloc_tallies)
+            // SAFETY: Same as above.
+            let teardown_interval = teardown_interval.map(|[start, end]| unsafe {
+                [start.into_timestamp(timer_kind), end.into_timestamp(timer_kind)]
+            });
+
+            (interval, teardown_interval, alloc_tallies)
         }
     }
 
+    /// Separately samples `gen_input` if `--measure-setup` is set, using the
+    /// sample size and count of the benchmark's collected samples.
+    ///
+    /// Inputs are dropped outside of the timed section.
     #[inline]
+    fn measure_setup<I>(&mut self, mut gen_input: impl FnMut() -> I) {
+        if !self.shared_context.measure_setup || self.shared_context.action.is_test() {
+            return;
+        }
+
+        let timer = self.shared_context.timer;
+        let timer_kind = timer.kind();
+
+        let sample_size = self.samples.sample_size;
+        let sample_count = self.samples.time_samples.len();
+
+        self.setup_samples.sample_size = sample_size;
+        self.setup_samples.time_samples.reserve(sample_count);
+
+        let mut inputs = Vec::<I>::with_capacity(sample_size as usize);
+
+        let setup_start = Instant::now();
+
+        for _ in 0..sample_count {
+            if crate::interrupt::is_interrupted() {
+                break;
+            }
+
+            let start = UntaggedTimestamp::start(timer_kind);
+
+            for _ in 0..sample_size {
+                inputs.push(gen_input());
+            }
+
+            let end = UntaggedTimestamp::end(timer_kind);
+
+            // SAFETY: Both timestamps were created with `timer_kind`.
+            let duration = unsafe {
+                end.into_timestamp(timer_kind)
+                    .duration_since(start.into_timestamp(timer_kind), timer)
+            };
+
+            self.setup_samples.time_samples.push(TimeSample { duration });
+            inputs.clear();
+        }
+
+        self.phase_times.setup += FineDuration::from(setup_start.elapsed());
+    }
+
     fn initial_mode(&self) -> BenchMode {
         if self.shared_context.action.is_test() {
             BenchMode::Test
@@ -1106,6 +1634,26 @@ impl<'a> BenchContext<'a> {
         }
     }
 
+    /// Returns the most allocations made by an iteration, including the
+    /// single run of `--test`.
+    pub fn max_allocs_per_iter(&self) -> u64 {
+        self.samples.max_allocs_per_iter()
+    }
+
+    /// Returns `true` if any samples were recorded.
+    pub fn has_samples(&self) -> bool {
+        !self.samples.time_samples.is_empty()
+    }
+
+    /// Returns statistics of recorded samples, or `None` if there are none.
+    pub fn sample_stats(&self) -> Option<SampleStats> {
+        if !self.has_samples() {
+            return None;
+        }
+
+        Some(self.compute_stats().to_sample_stats())
+    }
+
     pub fn compute_stats(&self) -> Stats {
         let time_samples = &self.samples.time_samples;
         let alloc_samples = &self.samples.alloc_tallies;
@@ -1115,11 +1663,6 @@ impl<'a> BenchContext<'a> {
 
         let total_count = self.samples.iter_count();
 
-        let total_duration = self.samples.total_duration();
-        let mean_duration = FineDuration {
-            picos: total_duration.picos.checked_div(total_count as u128).unwrap_or_default(),
-        };
-
         // Samples sorted by duration.
         let sorted_samples = self.samples.sorted_samples();
         let median_samples = util::slice_middle(&sorted_samples);
@@ -1141,18 +1684,6 @@ impl<'a> BenchContext<'a> {
                 counts.get(index).copied()
             };
 
-        let min_duration =
-            sorted_samples.first().map(|s| s.duration / sample_size).unwrap_or_default();
-        let max_duration =
-            sorted_samples.last().map(|s| s.duration / sample_size).unwrap_or_default();
-
-        let median_duration = if median_samples.is_empty() {
-            FineDuration::default()
-        } else {
-            let sum: u128 = median_samples.iter().map(|s| s.duration.picos).sum();
-            FineDuration { picos: sum / median_samples.len() as u128 } / sample_size
-        };
-
         let counts = KnownCounterKind::ALL.map(|counter_kind| {
             let median: MaxCountUInt = {
                 let mut sum: u128 = 0;
@@ -1164,7 +1695,7 @@ impl<'a> BenchContext<'a> {
                     sum = sum.saturating_add(sample_count);
                 }
 
-                (sum / median_samples.len() as u128) as MaxCountUInt
+                (sum / median_samples.len().max(1) as u128) as MaxCountUInt
             };
 
             Some(StatsSet {
@@ -1196,12 +1727,13 @@ impl<'a> BenchContext<'a> {
         Stats {
             sample_count: sample_count as u32,
             iter_count: total_count,
-            time: StatsSet {
-                fastest: min_duration,
-                slowest: max_duration,
-                median: median_duration,
-                mean: mean_duration,
-            },
+            time: self.samples.time_stats(),
+            trimmed_mean_time: self.samples.trimmed_mean_time(),
+            iqr_time: self.samples.iqr_time(),
+            mad_time: self.samples.mad_time(),
+            std_dev_time: self.samples.std_dev_time(),
+            p99_time: self.samples.quantile_time(990),
+            p999_time: self.samples.quantile_time(999),
             alloc_tallies: AllocOpMap {
                 values: AllocOp::ALL
                     .map(|op| StatsSet {
@@ -1250,6 +1782,18 @@ impl<'a> BenchContext<'a> {
                     .map(StatsSet::transpose),
             },
             counts,
+            setup_time: if self.setup_samples.time_samples.is_empty() {
+                None
+            } else {
+                Some(self.setup_samples.time_stats())
+            },
+            teardown_time: if self.teardown_samples.time_samples.is_empty() {
+                None
+            } else {
+                Some(self.teardown_samples.time_stats())
+            },
+            drift: self.samples.drift(),
+            cold_time: self.cold_time,
         }
     }
 }
@@ -15,6 +15,11 @@ pub struct BenchOptions<'a> {
     /// The number of iterations inside a single sample.
     pub sample_size: Option<u32>,
 
+    /// The number of initial samples to discard from statistics.
+    ///
+    /// Unlike warmup, these samples are timed as part of `sample_count`.
+    pub drop_first: Option<u32>,
+
     /// The number of threads to benchmark the sample. This is 1 by default.
     ///
     /// If set to 0, this will use [`std::thread::available_parallelism`].
@@ -33,11 +38,24 @@ pub struct BenchOptions<'a> {
     /// The time ceiling for benchmarking a function.
     pub max_time: Option<Duration>,
 
+    /// The minimum number of samples to collect, even if it exceeds
+    /// `max_time`.
+    pub min_samples: Option<u32>,
+
     /// When accounting for `min_time` or `max_time`, skip time external to
     /// benchmarked functions, such as time spent generating inputs and running
     /// [`Drop`].
     pub skip_ext_time: Option<bool>,
 
+    /// Labels for selecting benchmarks independently of their paths.
+    ///
+    /// Unlike other options, tags are combined with those of parent groups
+    /// rather than overwriting them.
+    pub tags: Option<Cow<'a, [&'static str]>>,
+
+    /// The most heap allocations allowed per iteration before failing.
+    pub max_allocs: Option<u64>,
+
     /// Whether the benchmark should be ignored.
     ///
     /// This may be set within the attribute or with a separate
@@ -56,14 +74,41 @@ impl<'a> BenchOptions<'a> {
             // `Copy` values:
             sample_count: self.sample_count.or(other.sample_count),
             sample_size: self.sample_size.or(other.sample_size),
+            drop_first: self.drop_first.or(other.drop_first),
             threads: self.threads.as_deref().or(other.threads.as_deref()).map(Cow::Borrowed),
             min_time: self.min_time.or(other.min_time),
             max_time: self.max_time.or(other.max_time),
+            min_samples: self.min_samples.or(other.min_samples),
             skip_ext_time: self.skip_ext_time.or(other.skip_ext_time),
+            max_allocs: self.max_allocs.or(other.max_allocs),
             ignore: self.ignore.or(other.ignore),
 
             // `Clone` values:
             counters: self.counters.overwrite(&other.counters),
+
+            // Combined values:
+            tags: match (self.tags.as_deref(), other.tags.as_deref()) {
+                (None, None) => None,
+                (Some(tags), None) | (None, Some(tags)) => Some(Cow::Borrowed(tags)),
+                (Some(tags), Some(other_tags)) => {
+                    let mut all_tags = other_tags.to_vec();
+                    all_tags.extend(tags.iter().filter(|tag| !other_tags.contains(tag)));
+                    Some(Cow::Owned(all_tags))
+                }
+            },
+        }
+    }
+
+    /// Returns `true` if any of `tags` is set, or all of `tags` if
+    /// `match_all` is `true`.
+    pub(crate) fn has_tags<T: AsRef<str>>(&self, tags: &[T], match_all: bool) -> bool {
+        let own_tags = self.tags.as_deref().unwrap_or_default();
+        let has_tag = |tag: &T| own_tags.contains(&tag.as_ref());
+
+        if match_all {
+            tags.iter().all(has_tag)
+        } else {
+            tags.iter().any(has_tag)
         }
     }
 
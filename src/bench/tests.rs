@@ -4,6 +4,7 @@
 use std::{
     collections::HashSet,
     sync::atomic::{AtomicUsize, Ordering::SeqCst},
+    time::Duration,
 };
 
 use super::*;
@@ -31,6 +32,19 @@ const THREAD_COUNTS: &[usize] = if cfg!(miri) {
     &[1, 2, 3, 4, 5, 6, 9]
 };
 
+/// Returns a context for `action` with the OS timer and all other options
+/// disabled, to be customized via struct update syntax.
+pub(crate) fn shared_context(action: Action) -> SharedContext {
+    SharedContext {
+        action,
+        timer: Timer::Os,
+        bench_overhead: FineDuration::default(),
+        measure_setup: false,
+        deterministic: false,
+        measure_cold: false,
+    }
+}
+
 #[track_caller]
 fn test_bencher(test: &mut dyn FnMut(Bencher)) {
     let bench_options = BenchOptions {
@@ -41,8 +55,7 @@ fn test_bencher(test: &mut dyn FnMut(Bencher)) {
 
     for timer in Timer::available() {
         for action in [Action::Bench, Action::Test] {
-            let shared_context =
-                SharedContext { action, timer, bench_overhead: FineDuration::default() };
+            let shared_context = SharedContext { timer, ..shared_context(action) };
 
             for &thread_count in THREAD_COUNTS {
                 let mut bench_context = BenchContext::new(
@@ -550,3 +563,478 @@ mod zst_input {
         assert_eq!(ZST_COUNT.load(SeqCst), 0);
     }
 }
+
+/// Tests that by-value inputs are generated before each sample is timed and
+/// that returned outputs are dropped after.
+#[test]
+fn values_ownership() {
+    use std::sync::Mutex;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Event {
+        Gen,
+        Bench,
+        Drop,
+    }
+
+    static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+
+    fn push_event(event: Event) {
+        EVENTS.lock().unwrap().push(event);
+    }
+
+    struct Output;
+
+    impl Drop for Output {
+        fn drop(&mut self) {
+            push_event(Event::Drop);
+        }
+    }
+
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    let shared_context = shared_context(Action::Bench);
+
+    let expected: Vec<Event> = (0..SAMPLE_COUNT)
+        .flat_map(|_| {
+            [Event::Gen, Event::Bench, Event::Drop]
+                .into_iter()
+                .flat_map(|event| std::iter::repeat(event).take(SAMPLE_SIZE as usize))
+        })
+        .collect();
+
+    let gen_input = || {
+        push_event(Event::Gen);
+        Output
+    };
+
+    let benched = |input: Output| {
+        push_event(Event::Bench);
+        input
+    };
+
+    for is_local in [false, true] {
+        EVENTS.lock().unwrap().clear();
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        let bencher = Bencher::new(&mut bench_context).with_inputs(gen_input);
+
+        if is_local {
+            bencher.bench_local_values(benched);
+        } else {
+            bencher.bench_values(benched);
+        }
+
+        assert_eq!(*EVENTS.lock().unwrap(), expected);
+    }
+}
+
+/// Tests that `drop_first` discards samples while keeping at least one.
+#[test]
+fn drop_first() {
+    for (drop_first, expected_count) in [(0, SAMPLE_COUNT), (1, SAMPLE_COUNT - 1), (100, 1)] {
+        let bench_options = BenchOptions {
+            sample_count: Some(SAMPLE_COUNT),
+            sample_size: Some(SAMPLE_SIZE),
+            drop_first: Some(drop_first),
+            ..BenchOptions::default()
+        };
+
+        let shared_context = shared_context(Action::Bench);
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context).bench(|| {});
+
+        assert_eq!(bench_context.samples.time_samples.len(), expected_count as usize);
+
+        // Reported iterations only include kept samples.
+        let stats = bench_context.compute_stats();
+        assert_eq!(stats.sample_count, expected_count);
+        assert_eq!(stats.iter_count, expected_count as u64 * SAMPLE_SIZE as u64);
+    }
+}
+
+/// Tests that `--measure-setup` separately samples only `with_inputs`.
+#[test]
+fn measure_setup() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    let shared_context = SharedContext { measure_setup: true, ..shared_context(Action::Bench) };
+
+    let gen_count = AtomicUsize::new(0);
+
+    let mut bench_context = BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+    Bencher::new(&mut bench_context)
+        .with_inputs(|| {
+            gen_count.fetch_add(1, SeqCst);
+            String::from("input")
+        })
+        .bench_values(drop);
+
+    // Inputs are generated once for the benchmark and once for setup.
+    let iter_count = (SAMPLE_COUNT * SAMPLE_SIZE) as usize;
+    assert_eq!(gen_count.load(SeqCst), iter_count * 2);
+    assert_eq!(bench_context.setup_samples.time_samples.len(), SAMPLE_COUNT as usize);
+    assert!(bench_context.compute_stats().setup_time.is_some());
+
+    // Benchmarks without inputs have no setup to measure.
+    let mut bench_context = BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+    Bencher::new(&mut bench_context).bench(|| {});
+
+    assert!(bench_context.compute_stats().setup_time.is_none());
+}
+
+/// Tests that `with_teardown` releases every output and is sampled separately.
+#[test]
+fn teardown() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    for action in [Action::Bench, Action::Test] {
+        let shared_context = shared_context(action);
+
+        let (iter_count, sample_count) = if action.is_test() {
+            (1, 0)
+        } else {
+            ((SAMPLE_COUNT * SAMPLE_SIZE) as usize, SAMPLE_COUNT as usize)
+        };
+
+        // Outputs that need drop.
+        let teardown_count = AtomicUsize::new(0);
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context)
+            .with_inputs(|| String::from("input"))
+            .with_teardown(|s: String| {
+                drop(s);
+                teardown_count.fetch_add(1, SeqCst);
+            })
+            .bench_values(|s| s);
+
+        assert_eq!(teardown_count.load(SeqCst), iter_count);
+        assert_eq!(bench_context.teardown_samples.time_samples.len(), sample_count);
+
+        // Zero-sized outputs that don't need drop.
+        let teardown_count = AtomicUsize::new(0);
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context)
+            .with_inputs(|| ())
+            .with_teardown(|()| {
+                teardown_count.fetch_add(1, SeqCst);
+            })
+            .bench_local_refs(|_| {});
+
+        assert_eq!(teardown_count.load(SeqCst), iter_count);
+        assert_eq!(bench_context.teardown_samples.time_samples.len(), sample_count);
+    }
+
+    // Benchmarks without teardown have no teardown to measure.
+    let shared_context = shared_context(Action::Bench);
+
+    let mut bench_context = BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+    Bencher::new(&mut bench_context).with_inputs(|| String::from("input")).bench_values(|s| s);
+
+    assert!(bench_context.compute_stats().teardown_time.is_none());
+}
+
+/// Tests that `with_consume` receives every output without sampling it.
+#[test]
+fn consume() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    for action in [Action::Bench, Action::Test] {
+        let shared_context = shared_context(action);
+
+        let iter_count = if action.is_test() { 1 } else { (SAMPLE_COUNT * SAMPLE_SIZE) as usize };
+
+        let mut consume_count = 0;
+        let mut consume_sum = 0;
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context)
+            .with_inputs(|| 2)
+            .with_consume(|n: usize| {
+                consume_count += 1;
+                consume_sum += n;
+            })
+            .bench_values(|n| n * 3);
+
+        assert_eq!(consume_count, iter_count);
+        assert_eq!(consume_sum, iter_count * 6);
+        assert!(bench_context.teardown_samples.time_samples.is_empty());
+    }
+}
+
+/// Tests that `bench_measured` returns statistics of recorded samples.
+#[test]
+fn bench_measured() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    for action in [Action::Bench, Action::Test] {
+        let shared_context = shared_context(action);
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        let stats = Bencher::new(&mut bench_context).bench_measured(|| {});
+
+        if action.is_test() {
+            assert!(stats.is_none());
+        } else {
+            let stats = stats.unwrap();
+            assert_eq!(stats.sample_count, SAMPLE_COUNT);
+            assert_eq!(stats.iter_count, (SAMPLE_COUNT * SAMPLE_SIZE) as u64);
+            assert!(stats.fastest <= stats.median && stats.median <= stats.slowest);
+        }
+    }
+}
+
+/// Tests that deterministic runs collect all samples regardless of time limits.
+#[test]
+fn deterministic() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        max_time: Some(Duration::ZERO),
+        ..BenchOptions::default()
+    };
+
+    for deterministic in [false, true] {
+        let shared_context = SharedContext { deterministic, ..shared_context(Action::Bench) };
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context).bench(|| {});
+
+        let expected_count = if deterministic { SAMPLE_COUNT as usize } else { 0 };
+        assert_eq!(bench_context.samples.time_samples.len(), expected_count);
+    }
+}
+
+/// Tests that `min_samples` is collected despite a depleted `max_time`.
+#[test]
+fn min_samples() {
+    let shared_context = shared_context(Action::Bench);
+
+    for (min_samples, expected_count) in [(0, 0), (5, 5), (SAMPLE_COUNT * 2, SAMPLE_COUNT * 2)] {
+        let bench_options = BenchOptions {
+            sample_count: Some(SAMPLE_COUNT),
+            sample_size: Some(SAMPLE_SIZE),
+            max_time: Some(Duration::ZERO),
+            min_samples: Some(min_samples),
+            ..BenchOptions::default()
+        };
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context).bench(|| {});
+
+        assert_eq!(bench_context.samples.time_samples.len(), expected_count as usize);
+    }
+}
+
+/// Tests that reaching `max_time` before `sample_count` is reported.
+#[test]
+fn hit_max_time() {
+    let shared_context = shared_context(Action::Bench);
+
+    for (sample_count, expected_hit) in [(SAMPLE_COUNT, false), (1_000_000, true)] {
+        let bench_options = BenchOptions {
+            sample_count: Some(sample_count),
+            sample_size: Some(SAMPLE_SIZE),
+            max_time: Some(Duration::from_millis(1)),
+            ..BenchOptions::default()
+        };
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context).bench(|| {});
+
+        assert_eq!(bench_context.hit_max_time, expected_hit);
+    }
+}
+
+/// Tests that the first call is timed separately before sampling.
+#[test]
+fn cold_time() {
+    const COLD_TIME: Duration = Duration::from_millis(5);
+
+    for measure_cold in [false, true] {
+        let shared_context = SharedContext { measure_cold, ..shared_context(Action::Bench) };
+
+        let bench_options = BenchOptions {
+            sample_count: Some(SAMPLE_COUNT),
+            sample_size: Some(SAMPLE_SIZE),
+            ..BenchOptions::default()
+        };
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        // Only the first call is slow, as with lazy initialization.
+        let is_initialized = std::cell::Cell::new(false);
+        Bencher::new(&mut bench_context).bench_local(|| {
+            if !is_initialized.replace(true) {
+                std::thread::sleep(COLD_TIME);
+            }
+        });
+
+        let stats = bench_context.compute_stats();
+
+        if measure_cold {
+            assert!(stats.cold_time.unwrap() >= FineDuration::from(COLD_TIME));
+            assert!(stats.time.slowest < FineDuration::from(COLD_TIME));
+        } else {
+            assert_eq!(stats.cold_time, None);
+        }
+    }
+}
+
+/// Tests that benchmarks without samples compute statistics without panicking.
+#[test]
+fn no_samples() {
+    let shared_context = shared_context(Action::Bench);
+
+    let bench_options = BenchOptions { sample_count: Some(0), ..BenchOptions::default() };
+
+    for thread_count in THREAD_COUNTS.iter().map(|&n| NonZeroUsize::new(n).unwrap()) {
+        let mut bench_context = BenchContext::new(&shared_context, &bench_options, thread_count);
+
+        Bencher::new(&mut bench_context)
+            .counter(crate::counter::BytesCount::new(8usize))
+            .bench(make_string);
+
+        assert!(bench_context.did_run);
+        assert!(!bench_context.has_samples());
+        assert!(bench_context.sample_stats().is_none());
+
+        let stats = bench_context.compute_stats();
+        assert_eq!(stats.sample_count, 0);
+        assert_eq!(stats.time.median, FineDuration::default());
+    }
+}
+
+/// Tests that `Bencher::skip` does not run the benchmark.
+#[test]
+fn skip() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    for action in [Action::Bench, Action::Test] {
+        let shared_context = shared_context(action);
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        Bencher::new(&mut bench_context).skip();
+
+        assert!(bench_context.skipped);
+        assert!(!bench_context.did_run);
+        assert!(!bench_context.has_samples());
+    }
+}
+
+/// Tests that allocations per iteration are counted for `max_allocs`, including
+/// by the single run of `--test`.
+#[test]
+fn max_allocs_per_iter() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    for action in [Action::Bench, Action::Test] {
+        let shared_context = shared_context(action);
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+        Bencher::new(&mut bench_context).bench_local(|| black_box(1) + 1);
+        assert_eq!(bench_context.max_allocs_per_iter(), 0);
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+        Bencher::new(&mut bench_context).bench_local(|| black_box(vec![1u8]));
+        assert_eq!(bench_context.max_allocs_per_iter(), 1);
+    }
+}
+
+/// Tests that `Bencher::verify` checks the result once before benchmarking.
+#[test]
+fn verify() {
+    let bench_options = BenchOptions {
+        sample_count: Some(SAMPLE_COUNT),
+        sample_size: Some(SAMPLE_SIZE),
+        ..BenchOptions::default()
+    };
+
+    for action in [Action::Bench, Action::Test] {
+        let shared_context = shared_context(action);
+
+        let iter_count = if action.is_test() { 1 } else { (SAMPLE_COUNT * SAMPLE_SIZE) as usize };
+
+        let mut bench_context =
+            BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+        let mut call_count = 0;
+        Bencher::new(&mut bench_context).verify(42, || {
+            call_count += 1;
+            42
+        });
+
+        assert_eq!(call_count, iter_count + 1);
+    }
+}
+
+/// Tests that `Bencher::verify` fails on an unexpected result without
+/// benchmarking.
+#[test]
+#[should_panic = "benchmark result does not match expected value"]
+fn verify_mismatch() {
+    let shared_context = shared_context(Action::Bench);
+
+    let bench_options = BenchOptions::default();
+
+    let mut bench_context = BenchContext::new(&shared_context, &bench_options, NonZeroUsize::MIN);
+
+    Bencher::new(&mut bench_context).verify(1, || 2);
+}
@@ -1,7 +1,10 @@
 use clap::{builder::PossibleValue, value_parser, Arg, ArgAction, ColorChoice, Command, ValueEnum};
 
 use crate::{
-    config::{ParsedSeconds, SortingAttr},
+    config::{
+        ParsedBandwidth, ParsedDuration, ParsedScoped, ParsedSeconds, PrimaryStat, Priority,
+        SortingAttr,
+    },
     counter::MaxCountUInt,
     time::TimerKind,
 };
@@ -55,6 +58,55 @@ pub(crate) fn command() -> Command {
                 .action(ArgAction::Append),
         )
         .arg(flag("exact").help("Filter benchmarks by exact name rather than by pattern"))
+        .arg(
+            option("tag")
+                .env("DIVAN_TAG")
+                .value_name("TAG")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .help("Only run benchmarks with this tag"),
+        )
+        .arg(flag("all-tags").help("Only run benchmarks with all tags given by --tag, rather than any"))
+        .arg(
+            option("changed-files")
+                .env("DIVAN_CHANGED_FILES")
+                .value_name("PATH")
+                .help("Only run benchmarks defined in source files listed one per line in this file"),
+        )
+        .arg(
+            flag("allow-duplicate-names")
+                .help("Run benchmarks even if multiple have the same path"),
+        )
+        .arg(
+            flag("allow-debug-build")
+                .env("DIVAN_ALLOW_DEBUG_BUILD")
+                .help("Benchmark debug builds without warning"),
+        )
+        .arg(
+            flag("allow-frequency-scaling")
+                .env("DIVAN_ALLOW_FREQUENCY_SCALING")
+                .help("Benchmark without warning about CPU frequency governors and turbo boost"),
+        )
+        .arg(
+            flag("check-stability")
+                .env("DIVAN_CHECK_STABILITY")
+                .help("Warn if timing a fixed workload varies too much for reliable results before running"),
+        )
+        .arg(
+            option("priority")
+                .env("DIVAN_PRIORITY")
+                .value_name("PRIORITY")
+                .help("Raise the scheduling priority of benchmarks")
+                .long_help(
+                    "Raise the scheduling priority of benchmarks to reduce noise from other \
+                     processes. Requires the `priority` crate feature.\n\n\
+                     'high' lowers the niceness of the benchmark process. 'realtime' uses \
+                     SCHED_FIFO, which usually requires root; a benchmark that never yields can \
+                     then make the machine unresponsive until it finishes.\n\n\
+                     If the priority cannot be raised, benchmarks run at normal priority.",
+                )
+                .value_parser(value_parser!(Priority)),
+        )
         .arg(flag("ignored").help("Run only ignored benchmarks").conflicts_with("include-ignored"))
         .arg(
             flag("include-ignored")
@@ -76,6 +128,19 @@ pub(crate) fn command() -> Command {
                 .value_parser(value_parser!(SortingAttr))
                 .overrides_with("sort"),
         )
+        .arg(
+            flag("deterministic")
+                .env("DIVAN_DETERMINISTIC")
+                .help("Only use explicit sample counts and sizes, without tuning to machine speed or time limits"),
+        )
+        .arg(
+            option("shuffle")
+                .env("DIVAN_SHUFFLE")
+                .value_name("SEED")
+                .help("Run benchmarks in random order to detect order effects, optionally with a seed")
+                .value_parser(value_parser!(u64))
+                .num_args(0..=1),
+        )
         .arg(
             option("timer")
                 .env("DIVAN_TIMER")
@@ -86,15 +151,29 @@ pub(crate) fn command() -> Command {
         .arg(
             option("sample-count")
                 .env("DIVAN_SAMPLE_COUNT")
-                .value_name("N")
-                .help("Set the number of sampling iterations")
-                .value_parser(value_parser!(u32)),
+                .value_name("[PATH=]N")
+                .help("Set the number of sampling iterations, optionally only for benchmarks under PATH")
+                .value_parser(value_parser!(ParsedScoped<u32>))
+                .action(ArgAction::Append),
         )
         .arg(
             option("sample-size")
                 .env("DIVAN_SAMPLE_SIZE")
+                .value_name("[PATH=]N")
+                .help("Set the number of iterations inside a single sample, optionally only for benchmarks under PATH")
+                .value_parser(value_parser!(ParsedScoped<u32>))
+                .action(ArgAction::Append),
+        )
+        .arg(
+            flag("latency")
+                .env("DIVAN_LATENCY")
+                .help("Take many single-iteration samples and show p99 and p99.9 times"),
+        )
+        .arg(
+            option("drop-first")
+                .env("DIVAN_DROP_FIRST")
                 .value_name("N")
-                .help("Set the number of iterations inside a single sample")
+                .help("Discard the first N samples from statistics, after timing them")
                 .value_parser(value_parser!(u32)),
         )
         .arg(
@@ -120,6 +199,20 @@ pub(crate) fn command() -> Command {
                 .help("Set the maximum seconds spent benchmarking a single function, with priority over '--min-time'")
                 .value_parser(value_parser!(ParsedSeconds)),
         )
+        .arg(
+            option("deadline")
+                .env("DIVAN_DEADLINE")
+                .value_name("DURATION")
+                .help("Reduce sampling to finish the entire run within a time budget, such as 600s")
+                .value_parser(value_parser!(ParsedDuration)),
+        )
+        .arg(
+            option("min-samples")
+                .env("DIVAN_MIN_SAMPLES")
+                .value_name("N")
+                .help("Set the minimum number of samples to collect, with priority over '--max-time'")
+                .value_parser(value_parser!(u32)),
+        )
         .arg(
             option("skip-ext-time")
                 .env("DIVAN_SKIP_EXT_TIME")
@@ -128,6 +221,11 @@ pub(crate) fn command() -> Command {
                 .value_parser(value_parser!(bool))
                 .num_args(0..=1),
         )
+        .arg(
+            flag("measure-setup")
+                .env("DIVAN_MEASURE_SETUP")
+                .help("Separately measure time spent generating inputs via 'Bencher::with_inputs'"),
+        )
         .arg(
             option("items-count")
                 .env("DIVAN_ITEMS_COUNT")
@@ -144,11 +242,137 @@ pub(crate) fn command() -> Command {
         )
         .arg(
             option("bytes-format")
+                .visible_alias("byte-units")
                 .env("DIVAN_BYTES_FORMAT")
                 .help("Set the numerical base for bytes in output")
                 .value_name("decimal|binary")
                 .value_parser(value_parser!(crate::counter::PrivBytesFormat))
         )
+        .arg(
+            option("peak-bandwidth")
+                .env("DIVAN_PEAK_BANDWIDTH")
+                .value_name("RATE")
+                .help("Show byte throughput as a percentage of this peak, such as 25GB/s")
+                .value_parser(value_parser!(ParsedBandwidth)),
+        )
+        .arg(
+            option("columns")
+                .env("DIVAN_COLUMNS")
+                .value_name("LIST")
+                .help("Set which columns to show, in order, as a comma-separated list")
+                .value_delimiter(','),
+        )
+        .arg(
+            flag("show-iqr")
+                .env("DIVAN_SHOW_IQR")
+                .help("Show the interquartile range of times as a column"),
+        )
+        .arg(
+            flag("show-mad")
+                .env("DIVAN_SHOW_MAD")
+                .help("Show the median absolute deviation of times as a column"),
+        )
+        .arg(
+            flag("show-cold")
+                .env("DIVAN_SHOW_COLD")
+                .help("Show the time of the first call before sampling as a column"),
+        )
+        .arg(
+            flag("summary")
+                .env("DIVAN_SUMMARY")
+                .help("Print the fastest and slowest benchmarks after running"),
+        )
+        .arg(
+            option("summary-top")
+                .env("DIVAN_SUMMARY_TOP")
+                .value_name("N")
+                .help("Set the number of benchmarks listed by --summary, implying --summary")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            option("relative-to")
+                .env("DIVAN_RELATIVE_TO")
+                .value_name("PATH")
+                .help("Print times relative to the benchmark at this path after running"),
+        )
+        .arg(
+            option("primary-stat")
+                .env("DIVAN_PRIMARY_STAT")
+                .value_name("STAT")
+                .help("Set the statistic used to compare benchmarks in summaries")
+                .long_help(
+                    "Set the statistic used to compare benchmarks in summaries.\n\n\
+                     The median (default) is robust to outliers but ignores the values of most \
+                     samples. The mean uses every sample but is skewed by outliers such as from \
+                     preemption. The trimmed mean excludes the fastest and slowest 10% of \
+                     samples before averaging the rest. The fastest (or min) sample best \
+                     isolates uninterrupted execution, but is biased towards lucky outliers and \
+                     is less reproducible between runs.",
+                )
+                .value_parser(value_parser!(PrimaryStat)),
+        )
+        .arg(
+            flag("drift-analysis")
+                .env("DIVAN_DRIFT_ANALYSIS")
+                .help("Print how median times changed over the early, middle, and late samples after running"),
+        )
+        .arg(
+            option("tiers")
+                .env("DIVAN_TIERS")
+                .value_name("BOUNDS")
+                .help("Print benchmarks grouped into time tiers separated by these bounds after running")
+                .value_parser(value_parser!(ParsedDuration))
+                .value_delimiter(',')
+                .num_args(0..=1)
+                .default_missing_value("100ns,1ms"),
+        )
+        .arg(
+            option("stress")
+                .env("DIVAN_STRESS")
+                .value_name("N")
+                .help("Run each benchmark N separate times and print the spread of results after running")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            flag("time-breakdown")
+                .env("DIVAN_TIME_BREAKDOWN")
+                .help("Print how the total time was split between sampling, warmup, setup, and overhead after running"),
+        )
+        .arg(
+            flag("geomean")
+                .env("DIVAN_GEOMEAN")
+                .help("Print the geometric mean of times across all benchmarks after running"),
+        )
+        .arg(
+            option("snapshot-structure")
+                .env("DIVAN_SNAPSHOT_STRUCTURE")
+                .value_name("PATH")
+                .help("Write a timing-free listing of all benchmarks to this file"),
+        )
+        .arg(
+            option("check-structure")
+                .env("DIVAN_CHECK_STRUCTURE")
+                .value_name("PATH")
+                .help("Exit with an error if benchmarks differ from the listing in this file"),
+        )
+        .arg(
+            option("path-separator")
+                .env("DIVAN_PATH_SEPARATOR")
+                .value_name("STR")
+                .help("Set the separator between components of displayed benchmark paths"),
+        )
+        .arg(
+            option("strip-prefix")
+                .env("DIVAN_STRIP_PREFIX")
+                .value_name("PREFIX")
+                .help("Hide leading groups of the benchmark tree, such as my_crate::benchmarks"),
+        )
+        .arg(
+            flag("strip-common-prefix")
+                .env("DIVAN_STRIP_COMMON_PREFIX")
+                .help("Hide the leading groups shared by all benchmarks in the benchmark tree")
+                .conflicts_with("strip-prefix"),
+        )
         .arg(
             option("chars-count")
                 .env("DIVAN_CHARS_COUNT")
@@ -188,3 +412,28 @@ impl ValueEnum for SortingAttr {
         Some(PossibleValue::new(name))
     }
 }
+
+impl ValueEnum for Priority {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::High, Self::Realtime]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+impl ValueEnum for PrimaryStat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Median, Self::Mean, Self::TrimmedMean, Self::Fastest]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Median => PossibleValue::new("median"),
+            Self::Mean => PossibleValue::new("mean"),
+            Self::TrimmedMean => PossibleValue::new("trimmed-mean"),
+            Self::Fastest => PossibleValue::new("fastest").alias("min"),
+        })
+    }
+}
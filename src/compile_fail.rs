@@ -24,6 +24,79 @@
 //! fn bench() {}
 //! ```
 //!
+//! ```compile_fail
+//! #[divan::bench(args = [1], black_box_args, r#black_box_args)]
+//! fn bench(_: i32) {}
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench(args = [1], args_as_items, r#args_as_items)]
+//! fn bench(_: usize) {}
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench(should_panic, r#should_panic)]
+//! fn bench() {}
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench(black_box_return = false, r#black_box_return = false)]
+//! fn bench() {}
+//! ```
+//!
+//! # Missing Options
+//!
+//! `black_box_args` has no effect without `args`, so enabling it is rejected.
+//! An explicit `black_box_args = false` is allowed.
+//!
+//! ```compile_fail
+//! #[divan::bench(black_box_args)]
+//! fn bench() {}
+//! ```
+//!
+//! Likewise, `args_as_*` options count the value of `args`.
+//!
+//! ```compile_fail
+//! #[divan::bench(args_as_items)]
+//! fn bench() {}
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench_group(args_as_bytes)]
+//! mod group {}
+//! ```
+//!
+//! # Unsupported Options
+//!
+//! `should_panic` cannot catch panics within `Bencher` closures, and it is
+//! specific to each benchmark.
+//!
+//! ```compile_fail
+//! #[divan::bench(should_panic)]
+//! fn bench(bencher: divan::Bencher) {
+//!     bencher.bench(|| panic!());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench_group(should_panic)]
+//! mod group {}
+//! ```
+//!
+//! `black_box_return` does not apply to values returned by `Bencher` closures.
+//!
+//! ```compile_fail
+//! #[divan::bench(black_box_return = false)]
+//! fn bench(bencher: divan::Bencher) {
+//!     bencher.bench(|| 0);
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench_group(black_box_return = false)]
+//! mod group {}
+//! ```
+//!
 //! # Type Checking
 //!
 //! The following won't produce any benchmarks because `types = []`. However, we
@@ -34,3 +107,39 @@
 //! #[divan::bench(types = [], consts = ['a', 'b', 'c'])]
 //! fn bench<T, const C: i32>() {}
 //! ```
+//!
+//! # Input Lifetimes
+//!
+//! Inputs from `Bencher::with_inputs` are dropped after each sample, so
+//! references to them must not escape the benchmarked function, whether
+//! through its output or captured state.
+//!
+//! ```compile_fail
+//! #[divan::bench]
+//! fn bench(bencher: divan::Bencher) {
+//!     bencher.with_inputs(String::new).bench_refs(|s| s);
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench]
+//! fn bench(bencher: divan::Bencher) {
+//!     bencher.with_inputs(String::new).bench_local_refs(|s| s.as_str());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench]
+//! fn bench(bencher: divan::Bencher) {
+//!     let mut kept = Vec::new();
+//!     bencher.with_inputs(String::new).bench_local_refs(|s| kept.push(&*s));
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #[divan::bench]
+//! fn bench(bencher: divan::Bencher) {
+//!     let kept = std::sync::Mutex::new(Vec::new());
+//!     bencher.with_inputs(String::new).bench_refs(|s| kept.lock().unwrap().push(&*s));
+//! }
+//! ```
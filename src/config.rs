@@ -14,6 +14,124 @@ impl FromStr for ParsedSeconds {
     }
 }
 
+/// `Duration` wrapper for parsing durations with units from the CLI, such as
+/// `100ns` or `1.5ms`.
+#[derive(Clone, Copy)]
+pub(crate) struct ParsedDuration(pub Duration);
+
+impl FromStr for ParsedDuration {
+    type Err = Box<dyn Error + Send + Sync>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unit_start =
+            s.find(|c: char| c.is_alphabetic()).ok_or("missing time unit (ns, us, ms, or s)")?;
+
+        let (value, unit) = s.split_at(unit_start);
+
+        let unit_secs = match unit {
+            "ns" => 1e-9,
+            "us" | "µs" => 1e-6,
+            "ms" => 1e-3,
+            "s" => 1.0,
+            _ => return Err(format!("unknown time unit '{unit}' (ns, us, ms, or s)").into()),
+        };
+
+        Ok(Self(Duration::try_from_secs_f64(f64::from_str(value.trim())? * unit_secs)?))
+    }
+}
+
+/// Value parsed from the CLI that optionally applies only to benchmarks at or
+/// under a path, such as `500` or `math::fibonacci=500`.
+#[derive(Clone)]
+pub(crate) struct ParsedScoped<T> {
+    /// The `::`-separated benchmark or group path, or `None` for all
+    /// benchmarks.
+    pub path: Option<String>,
+    pub value: T,
+}
+
+impl<T: FromStr> FromStr for ParsedScoped<T>
+where
+    T::Err: Error + Send + Sync + 'static,
+{
+    type Err = Box<dyn Error + Send + Sync>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.rsplit_once('=') {
+            Some((path, value)) => {
+                let path = path.trim();
+                if path.is_empty() {
+                    return Err("missing path before '='".into());
+                }
+                Ok(Self { path: Some(path.to_owned()), value: value.trim().parse()? })
+            }
+            None => Ok(Self { path: None, value: s.trim().parse()? }),
+        }
+    }
+}
+
+/// Returns `true` if `path` is `scope` or a path under it.
+pub(crate) fn is_in_scope(path: &str, scope: &str) -> bool {
+    path.strip_prefix(scope).is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Returns `true` if source file paths `a` and `b` refer to the same file.
+///
+/// Paths may be relative to different directories, such as the workspace root
+/// for `file!()` and the repository root for `git diff --name-only`, or be
+/// absolute. So paths match if the shorter one is a suffix of the longer one
+/// at a component boundary. Both `/` and `\` are separators, and `.`
+/// components are ignored.
+pub(crate) fn is_same_file(a: &str, b: &str) -> bool {
+    fn components(path: &str) -> Vec<&str> {
+        path.split(['/', '\\']).filter(|c| !c.is_empty() && *c != ".").collect()
+    }
+
+    let (a, b) = (components(a), components(b));
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+
+    !shorter.is_empty() && longer.ends_with(&shorter)
+}
+
+/// Bandwidth in bytes per second parsed from the CLI, such as `25GB/s` or
+/// `12.8GiB/s`.
+#[derive(Clone, Copy)]
+pub(crate) struct ParsedBandwidth(pub f64);
+
+impl FromStr for ParsedBandwidth {
+    type Err = Box<dyn Error + Send + Sync>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_suffix("/s").unwrap_or(s);
+
+        let unit_start =
+            s.find(|c: char| c.is_alphabetic()).ok_or("missing bytes unit (e.g. GB or GiB)")?;
+
+        let (value, unit) = s.split_at(unit_start);
+
+        let unit_bytes = match unit {
+            "B" => 1.0,
+            "KB" => 1e3,
+            "MB" => 1e6,
+            "GB" => 1e9,
+            "TB" => 1e12,
+            "KiB" => 1024.0,
+            "MiB" => 1024.0 * 1024.0,
+            "GiB" => 1024.0 * 1024.0 * 1024.0,
+            "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            _ => return Err(format!("unknown bytes unit '{unit}' (e.g. GB or GiB)").into()),
+        };
+
+        let bandwidth = f64::from_str(value.trim())? * unit_bytes;
+
+        if bandwidth.is_finite() && bandwidth > 0.0 {
+            Ok(Self(bandwidth))
+        } else {
+            Err("bandwidth must be positive".into())
+        }
+    }
+}
+
 /// The primary action to perform.
 #[derive(Clone, Copy, Default)]
 pub(crate) enum Action {
@@ -182,3 +300,123 @@ impl SortingAttr {
         Ordering::Equal
     }
 }
+
+/// The statistic used to compare benchmarks in summaries.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum PrimaryStat {
+    /// Robust to outliers, but ignores the values of most samples.
+    #[default]
+    Median,
+
+    /// Uses every sample, but is skewed by outliers such as from preemption.
+    Mean,
+
+    /// Mean of samples excluding the fastest and slowest outliers.
+    TrimmedMean,
+
+    /// Closest to the cost without interference, but is the extreme tail.
+    Fastest,
+}
+
+impl PrimaryStat {
+    /// Returns the name used in summary titles.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Median => "median",
+            Self::Mean => "mean",
+            Self::TrimmedMean => "trimmed mean",
+            Self::Fastest => "minimum",
+        }
+    }
+}
+
+/// Scheduling priority of benchmarks, set via `--priority`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Priority {
+    /// Lowest niceness that leaves room for system services.
+    High,
+
+    /// `SCHED_FIFO`, which preempts all normal threads.
+    Realtime,
+}
+
+impl Priority {
+    /// Returns the name used in warnings.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Realtime => "realtime",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration() {
+        let parse = |s: &str| s.parse::<ParsedDuration>().map(|d| d.0).ok();
+
+        assert_eq!(parse("100ns"), Some(Duration::from_nanos(100)));
+        assert_eq!(parse("2us"), Some(Duration::from_micros(2)));
+        assert_eq!(parse("2µs"), Some(Duration::from_micros(2)));
+        assert_eq!(parse("1.5ms"), Some(Duration::from_micros(1500)));
+        assert_eq!(parse("3 s"), Some(Duration::from_secs(3)));
+
+        assert_eq!(parse("100"), None);
+        assert_eq!(parse("100m"), None);
+        assert_eq!(parse("-1ms"), None);
+    }
+
+    #[test]
+    fn parse_scoped() {
+        let parse = |s: &str| s.parse::<ParsedScoped<u32>>().map(|p| (p.path, p.value)).ok();
+
+        assert_eq!(parse("500"), Some((None, 500)));
+        assert_eq!(parse("math::fibonacci=500"), Some((Some("math::fibonacci".into()), 500)));
+        assert_eq!(parse("math = 5"), Some((Some("math".into()), 5)));
+
+        assert_eq!(parse("=500"), None);
+        assert_eq!(parse("math="), None);
+        assert_eq!(parse("math::fibonacci"), None);
+    }
+
+    #[test]
+    fn in_scope() {
+        assert!(is_in_scope("math::fibonacci", "math"));
+        assert!(is_in_scope("math::fibonacci", "math::fibonacci"));
+
+        assert!(!is_in_scope("math::fibonacci", "math::fib"));
+        assert!(!is_in_scope("math", "math::fibonacci"));
+    }
+
+    #[test]
+    fn same_file() {
+        assert!(is_same_file("benches/math.rs", "benches/math.rs"));
+        assert!(is_same_file("./benches/math.rs", "benches/math.rs"));
+        assert!(is_same_file("examples/benches/math.rs", "benches/math.rs"));
+        assert!(is_same_file("/home/user/crate/benches/math.rs", "benches/math.rs"));
+        assert!(is_same_file("benches\\math.rs", "benches/math.rs"));
+
+        assert!(!is_same_file("benches/math.rs", "benches/string.rs"));
+        assert!(!is_same_file("benches/math.rs", "s/math.rs"));
+        assert!(!is_same_file("benches/math.rs", "other/math.rs"));
+        assert!(!is_same_file("benches/math.rs", ""));
+    }
+
+    #[test]
+    fn parse_bandwidth() {
+        let parse = |s: &str| s.parse::<ParsedBandwidth>().map(|b| b.0).ok();
+
+        assert_eq!(parse("25GB/s"), Some(25e9));
+        assert_eq!(parse("1.5 MB/s"), Some(1.5e6));
+        assert_eq!(parse("2GiB/s"), Some(2.0 * 1024.0 * 1024.0 * 1024.0));
+        assert_eq!(parse("100B"), Some(100.0));
+
+        assert_eq!(parse("25"), None);
+        assert_eq!(parse("25Gb/s"), None);
+        assert_eq!(parse("0GB/s"), None);
+        assert_eq!(parse("-1GB/s"), None);
+    }
+}
@@ -100,6 +100,16 @@ impl KnownCounterKind {
             unreachable!()
         }
     }
+
+    /// The name of the counted values.
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bytes => "bytes",
+            Self::Chars => "chars",
+            Self::Items => "items",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -235,11 +235,18 @@ impl ItemsCount {
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[non_exhaustive]
 pub enum BytesFormat {
-    /// Powers of 1000, starting with KB (kilobyte). This is the default.
+    /// Powers of 1000, starting with KB (kilobyte), such as `MB/s`. This is
+    /// the default.
+    ///
+    /// This is also known as SI units and can be selected with
+    /// `--bytes-format si`.
     #[default]
     Decimal,
 
-    /// Powers of 1024, starting with KiB (kibibyte).
+    /// Powers of 1024, starting with KiB (kibibyte), such as `MiB/s`.
+    ///
+    /// This is also known as IEC units and can be selected with
+    /// `--bytes-format iec`.
     Binary,
 }
 
@@ -258,7 +265,11 @@ impl clap::ValueEnum for PrivBytesFormat {
             BytesFormat::Decimal => "decimal",
             BytesFormat::Binary => "binary",
         };
-        Some(clap::builder::PossibleValue::new(name))
+        let alias = match self.0 {
+            BytesFormat::Decimal => "si",
+            BytesFormat::Binary => "iec",
+        };
+        Some(clap::builder::PossibleValue::new(name).alias(alias))
     }
 }
 
@@ -0,0 +1,162 @@
+//! Detection of CPU frequency scaling, which makes times vary with load and
+//! temperature.
+//!
+//! This is advisory only. On Linux, it reads the following sysfs files and
+//! never writes to them:
+//!
+//! - `/sys/devices/system/cpu/cpu*/cpufreq/scaling_governor`
+//! - `/sys/devices/system/cpu/intel_pstate/no_turbo`
+//! - `/sys/devices/system/cpu/cpufreq/boost`
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// CPU frequency settings, if they can be read.
+pub(crate) struct CpuFreq {
+    /// Distinct scaling governors across CPUs, sorted.
+    pub governors: Vec<String>,
+
+    /// Whether turbo boost is enabled, if known.
+    pub turbo: Option<bool>,
+}
+
+impl CpuFreq {
+    /// Reads CPU frequency settings, or returns `None` if unsupported.
+    pub fn read() -> Option<Self> {
+        if cfg!(all(target_os = "linux", not(miri))) {
+            Self::read_from(Path::new("/sys/devices/system/cpu"))
+        } else {
+            None
+        }
+    }
+
+    /// Reads CPU frequency settings from a sysfs CPU directory.
+    fn read_from(cpu_dir: &Path) -> Option<Self> {
+        let read = |path: PathBuf| fs::read_to_string(path).ok();
+
+        let mut governors: Vec<String> = fs::read_dir(cpu_dir)
+            .ok()?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+
+                let name = entry.file_name();
+                let index = name.to_str()?.strip_prefix("cpu")?;
+                if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+
+                let governor = read(entry.path().join("cpufreq/scaling_governor"))?;
+                Some(governor.trim().to_owned())
+            })
+            .collect();
+
+        governors.sort_unstable();
+        governors.dedup();
+
+        let turbo = parse_turbo(
+            read(cpu_dir.join("intel_pstate/no_turbo")).as_deref(),
+            read(cpu_dir.join("cpufreq/boost")).as_deref(),
+        );
+
+        if governors.is_empty() && turbo.is_none() {
+            return None;
+        }
+
+        Some(Self { governors, turbo })
+    }
+
+    /// Returns governors that let the frequency vary, such as `powersave`.
+    pub fn scaling_governors(&self) -> impl Iterator<Item = &str> {
+        self.governors.iter().map(String::as_str).filter(|&governor| governor != "performance")
+    }
+}
+
+impl fmt::Display for CpuFreq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.governors.is_empty() {
+            write!(f, "{} governor", self.governors.join("/"))?;
+
+            if self.turbo.is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        match self.turbo {
+            Some(true) => f.write_str("turbo enabled"),
+            Some(false) => f.write_str("turbo disabled"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns whether turbo is enabled from the contents of `intel_pstate/no_turbo`
+/// or `cpufreq/boost`, preferring the former.
+fn parse_turbo(no_turbo: Option<&str>, boost: Option<&str>) -> Option<bool> {
+    match (no_turbo.map(str::trim), boost.map(str::trim)) {
+        (Some("0"), _) => Some(true),
+        (Some("1"), _) => Some(false),
+        (_, Some("1")) => Some(true),
+        (_, Some("0")) => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turbo() {
+        assert_eq!(parse_turbo(Some("0\n"), None), Some(true));
+        assert_eq!(parse_turbo(Some("1\n"), Some("1\n")), Some(false));
+        assert_eq!(parse_turbo(None, Some("1\n")), Some(true));
+        assert_eq!(parse_turbo(None, Some("0\n")), Some(false));
+        assert_eq!(parse_turbo(None, None), None);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // File system access.
+    fn read_from() {
+        let cpu_dir = std::env::temp_dir().join(format!("divan-cpufreq-{}", std::process::id()));
+        _ = fs::remove_dir_all(&cpu_dir);
+
+        let write = |path: &str, contents: &str| {
+            let path = cpu_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+
+        // No frequency scaling support, such as in virtual machines.
+        fs::create_dir_all(cpu_dir.join("cpu0")).unwrap();
+        assert!(CpuFreq::read_from(&cpu_dir).is_none());
+
+        write("cpu0/cpufreq/scaling_governor", "powersave\n");
+        write("cpu1/cpufreq/scaling_governor", "performance\n");
+        write("cpu2/cpufreq/scaling_governor", "powersave\n");
+        write("cpufreq/boost", "1\n");
+
+        // Not a CPU.
+        write("cpuidle/cpufreq/scaling_governor", "ondemand\n");
+
+        let freq = CpuFreq::read_from(&cpu_dir).unwrap();
+        assert_eq!(freq.governors, ["performance", "powersave"]);
+        assert_eq!(freq.scaling_governors().collect::<Vec<_>>(), ["powersave"]);
+        assert_eq!(freq.turbo, Some(true));
+
+        fs::remove_dir_all(&cpu_dir).unwrap();
+    }
+
+    #[test]
+    fn display() {
+        let freq = |governors: &[&str], turbo| {
+            CpuFreq { governors: governors.iter().map(|&g| g.to_owned()).collect(), turbo }
+                .to_string()
+        };
+
+        assert_eq!(freq(&["performance"], Some(false)), "performance governor, turbo disabled");
+        assert_eq!(freq(&["performance", "powersave"], None), "performance/powersave governor");
+        assert_eq!(freq(&[], Some(true)), "turbo enabled");
+    }
+}
@@ -1,35 +1,215 @@
 #![allow(clippy::too_many_arguments)]
 
-use std::{borrow::Cow, cell::RefCell, fmt, num::NonZeroUsize, time::Duration};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    fmt, mem,
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 use clap::ColorChoice;
 use regex::Regex;
 
 use crate::{
-    bench::BenchOptions,
-    config::{Action, Filter, ParsedSeconds, RunIgnored, SortingAttr},
+    bench::{BenchOptions, DEFAULT_SAMPLE_COUNT},
+    config::{
+        self, Action, Filter, ParsedBandwidth, ParsedDuration, ParsedScoped, ParsedSeconds,
+        PrimaryStat, Priority, RunIgnored, SortingAttr,
+    },
     counter::{
         BytesCount, BytesFormat, CharsCount, IntoCounter, ItemsCount, MaxCountUInt, PrivBytesFormat,
     },
+    cpufreq::CpuFreq,
     entry::{AnyBenchEntry, BenchEntryRunner, EntryTree},
+    interrupt,
+    priority::PriorityGuard,
+    structure,
+    summary::{Summary, SummaryFormat},
     time::{FineDuration, Timer, TimerKind},
     tree_painter::{TreeColumn, TreePainter},
-    util, Bencher,
+    util::{self, rand::Rng},
+    Bencher,
 };
 
+/// The number of benchmarks shown in each `--summary` list by default.
+const DEFAULT_SUMMARY_TOP: usize = 5;
+
+/// Samples within this multiple of the timer precision may be dominated by
+/// timer overhead.
+const OVERHEAD_BOUND_PRECISION_MULTIPLE: u128 = 10;
+
+/// The default number of samples with `--latency`, enough to resolve p99.9.
+const LATENCY_SAMPLE_COUNT: u32 = 10_000;
+
+/// The fewest samples that `--deadline` reduces benchmarks to.
+const DEADLINE_MIN_SAMPLES: u32 = 10;
+
 /// The benchmark runner.
+///
+/// An instance can be created from CLI arguments and environment variables via
+/// [`Divan::from_args`], or purely programmatically via [`Divan::new`] when
+/// embedded in a program that parses its own arguments.
+///
+/// # Options
+///
+/// Builder methods correspond to CLI arguments:
+///
+/// | Method | CLI argument |
+/// | ------ | ------------ |
+/// | [`filter_regex`](Self::filter_regex) | `<FILTER>` |
+/// | [`filter_exact`](Self::filter_exact) | `<FILTER> --exact` |
+/// | [`skip_regex`](Self::skip_regex) | `--skip <FILTER>` |
+/// | [`skip_exact`](Self::skip_exact) | `--skip <FILTER> --exact` |
+/// | [`tag`](Self::tag) | `--tag` |
+/// | [`match_all_tags`](Self::match_all_tags) | `--all-tags` |
+/// | [`changed_files`](Self::changed_files) | `--changed-files` |
+/// | [`allow_duplicate_names`](Self::allow_duplicate_names) | `--allow-duplicate-names` |
+/// | [`allow_debug_build`](Self::allow_debug_build) | `--allow-debug-build` |
+/// | [`allow_frequency_scaling`](Self::allow_frequency_scaling) | `--allow-frequency-scaling` |
+/// | [`check_stability`](Self::check_stability) | `--check-stability` |
+/// | [`priority`](Self::priority) | `--priority` |
+/// | [`run_ignored`](Self::run_ignored) | `--include-ignored` |
+/// | [`run_only_ignored`](Self::run_only_ignored) | `--ignored` |
+/// | [`test_benches`](Self::test_benches) | `--test` |
+/// | [`list_benches`](Self::list_benches) | `--list` |
+/// | [`color`](Self::color) | `--color` |
+/// | [`deterministic`](Self::deterministic) | `--deterministic` |
+/// | [`shuffle`](Self::shuffle) | `--shuffle` |
+/// | [`sort_by`](Self::sort_by) | `--sort`, `--sortr` |
+/// | [`timer`](Self::timer) | `--timer` |
+/// | [`sample_count`](Self::sample_count) | `--sample-count` |
+/// | [`sample_size`](Self::sample_size) | `--sample-size` |
+/// | [`latency`](Self::latency) | `--latency` |
+/// | [`drop_first`](Self::drop_first) | `--drop-first` |
+/// | [`threads`](Self::threads) | `--threads` |
+/// | [`min_time`](Self::min_time) | `--min-time` |
+/// | [`max_time`](Self::max_time) | `--max-time` |
+/// | [`deadline`](Self::deadline) | `--deadline` |
+/// | [`min_samples`](Self::min_samples) | `--min-samples` |
+/// | [`skip_ext_time`](Self::skip_ext_time) | `--skip-ext-time` |
+/// | [`measure_setup`](Self::measure_setup) | `--measure-setup` |
+/// | [`items_count`](Self::items_count) | `--items-count` |
+/// | [`bytes_count`](Self::bytes_count) | `--bytes-count` |
+/// | [`chars_count`](Self::chars_count) | `--chars-count` |
+/// | [`bytes_format`](Self::bytes_format) | `--bytes-format` |
+/// | [`peak_bandwidth`](Self::peak_bandwidth) | `--peak-bandwidth` |
+/// | [`summary`](Self::summary) | `--summary`, `--summary-top` |
+/// | [`primary_stat`](Self::primary_stat) | `--primary-stat` |
+/// | [`columns`](Self::columns) | `--columns` |
+/// | [`show_iqr`](Self::show_iqr) | `--show-iqr` |
+/// | [`show_mad`](Self::show_mad) | `--show-mad` |
+/// | [`show_cold`](Self::show_cold) | `--show-cold` |
+/// | [`relative_to`](Self::relative_to) | `--relative-to` |
+/// | [`drift_analysis`](Self::drift_analysis) | `--drift-analysis` |
+/// | [`tiers`](Self::tiers) | `--tiers` |
+/// | [`stress`](Self::stress) | `--stress` |
+/// | [`time_breakdown`](Self::time_breakdown) | `--time-breakdown` |
+/// | [`geomean`](Self::geomean) | `--geomean` |
+/// | [`snapshot_structure`](Self::snapshot_structure) | `--snapshot-structure` |
+/// | [`check_structure`](Self::check_structure) | `--check-structure` |
+/// | [`path_separator`](Self::path_separator) | `--path-separator` |
+/// | [`strip_prefix`](Self::strip_prefix) | `--strip-prefix` |
+/// | [`strip_common_prefix`](Self::strip_common_prefix) | `--strip-common-prefix` |
+///
+/// `--show-iqr` and `--show-mad` add columns for the interquartile range (IQR)
+/// and median absolute deviation (MAD) of times. Benchmark times are usually
+/// skewed by rare slow samples, such as from interrupts. Unlike the standard
+/// deviation, which assumes normally-distributed times and is dominated by
+/// such samples, IQR and MAD measure the spread of typical samples. MAD pairs
+/// with the median as a robust spread estimate.
+///
+/// `--show-cold` adds a column for the time of the first call of each
+/// benchmark, measured before sample size tuning and sampling, such as to see
+/// the cost of lazy initialization. This is a single observation, so it is
+/// inherently noisy. Since lazily-initialized state may be shared, only the
+/// first benchmark to use it observes the initialization cost.
+///
+/// `--priority high` lowers the niceness of the benchmark process, and
+/// `--priority realtime` runs it with the `SCHED_FIFO` policy, so that other
+/// processes are less likely to preempt benchmarks. This requires the
+/// `priority` crate feature and appropriate permissions, usually root for
+/// realtime. If the priority cannot be raised, Divan warns and runs benchmarks
+/// at normal priority. Realtime is never chosen unless requested explicitly,
+/// since a realtime benchmark that never yields can make the machine
+/// unresponsive until it finishes, such as a single-core machine or one with
+/// realtime throttling disabled.
 #[derive(Default)]
 pub struct Divan {
     action: Action,
     timer: TimerKind,
     reverse_sort: bool,
     sorting_attr: SortingAttr,
+    shuffle_seed: Option<u64>,
+    deterministic: bool,
     color: ColorChoice,
     bytes_format: BytesFormat,
+    peak_bandwidth: Option<f64>,
     filters: Vec<Filter>,
     skip_filters: Vec<Filter>,
+    tags: Vec<String>,
+    match_all_tags: bool,
+    changed_files: Option<PathBuf>,
+    allow_duplicate_names: bool,
+    allow_debug_build: bool,
+    allow_frequency_scaling: bool,
+    check_stability: bool,
+    priority: Option<Priority>,
     run_ignored: RunIgnored,
+    measure_setup: bool,
+    summary_top: Option<usize>,
+    path_separator: Option<String>,
+    strip_prefix: Option<String>,
+    strip_common_prefix: bool,
+    relative_to: Option<String>,
+    primary_stat: PrimaryStat,
+    columns: Option<Vec<TreeColumn>>,
+    drift_analysis: bool,
+    tiers: Option<Vec<FineDuration>>,
+    stress_runs: Option<usize>,
+    time_breakdown: bool,
+    geomean: bool,
+    snapshot_structure: Option<PathBuf>,
+    check_structure: Option<PathBuf>,
+    latency: bool,
+    deadline: Option<Duration>,
     bench_options: BenchOptions<'static>,
+    scoped_bench_options: Vec<(String, BenchOptions<'static>)>,
+    before_all: Mutex<Vec<Hook>>,
+    after_all: Mutex<Vec<Hook>>,
+}
+
+/// Function called once around the entire run.
+type Hook = Box<dyn FnOnce() + Send>;
+
+/// Time remaining for `--deadline`, which is split evenly between benchmarks
+/// that have yet to run.
+struct DeadlineBudget {
+    end: Instant,
+    remaining_runs: Cell<usize>,
+
+    /// Benchmarks that reached their share of the deadline before collecting
+    /// all samples.
+    reduced_count: Cell<usize>,
+}
+
+impl DeadlineBudget {
+    /// Returns the share of the remaining time for the next benchmark.
+    fn next_run(&self) -> Duration {
+        let runs = self.remaining_runs.get().max(1);
+        self.remaining_runs.set(runs - 1);
+
+        let remaining = self.end.saturating_duration_since(Instant::now());
+        remaining / u32::try_from(runs).unwrap_or(u32::MAX)
+    }
+
+    /// Gives up the shares of benchmarks that will not run, such as ignored
+    /// benchmarks, so that their time goes to the rest.
+    fn skip_runs(&self, runs: usize) {
+        self.remaining_runs.set(self.remaining_runs.get().saturating_sub(runs));
+    }
 }
 
 /// Immutable context shared between entry runs.
@@ -44,6 +224,16 @@ pub(crate) struct SharedContext {
     ///
     /// `min_time` and `max_time` do not consider this as benchmarking time.
     pub bench_overhead: FineDuration,
+
+    /// Whether to separately measure time spent generating inputs.
+    pub measure_setup: bool,
+
+    /// Whether to ignore `min_time` and `max_time` so that the number of
+    /// samples does not depend on machine speed.
+    pub deterministic: bool,
+
+    /// Whether to separately time the first call before sampling.
+    pub measure_cold: bool,
 }
 
 impl fmt::Debug for Divan {
@@ -75,7 +265,7 @@ impl Divan {
 
     /// Print registered functions as if the `--list` flag was used.
     pub fn list_benches(&self) {
-        self.run_action(Action::Test);
+        self.run_action(Action::List);
     }
 
     /// Returns `true` if an entry at the given path should be considered for
@@ -122,10 +312,98 @@ impl Divan {
             tree
         };
 
+        // Registration happens before `main` and silently does nothing if the
+        // target does not run initializer sections, which would otherwise look
+        // like there are no benchmarks defined.
+        if tree.is_empty() && !cfg!(miri) {
+            eprintln!("warning: No benchmarks were registered");
+            eprintln!("note: If `#[divan::bench]` is used, this target may not run pre-`main` initializer sections (`.init_array`, `__mod_init_func`, or `.CRT$XCU`) that register benchmarks");
+        }
+
+        // Check before filtering to catch duplicates regardless of which
+        // benchmarks are selected.
+        if !self.allow_duplicate_names {
+            let duplicates = EntryTree::duplicate_leaves(&tree);
+
+            for (path, [first, second]) in &duplicates {
+                eprintln!(
+                    "error: Multiple benchmarks named '{path}' at {}:{} and {}:{}",
+                    first.file, first.line, second.file, second.line,
+                );
+            }
+
+            if !duplicates.is_empty() {
+                eprintln!("note: Use `--allow-duplicate-names` to run anyway");
+                std::process::exit(1);
+            }
+        }
+
         // Filter after inserting groups so that we can properly use groups'
         // display names.
         EntryTree::retain(&mut tree, |entry_path| self.filter(entry_path));
 
+        if !self.tags.is_empty() {
+            EntryTree::retain_by_options(&mut tree, |options| {
+                options.has_tags(&self.tags, self.match_all_tags)
+            });
+        }
+
+        if let Some(path) = &self.changed_files {
+            let changed_files = std::fs::read_to_string(path).unwrap_or_else(|error| {
+                eprintln!("error: Failed to read changed files from '{}': {error}", path.display());
+                std::process::exit(1);
+            });
+
+            let changed_files: Vec<&str> =
+                changed_files.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+
+            EntryTree::retain_by_location(&mut tree, &mut |location| {
+                changed_files.iter().any(|file| config::is_same_file(location.file, file))
+            });
+        }
+
+        if self.snapshot_structure.is_some() || self.check_structure.is_some() {
+            // Sort by name so that the listing does not depend on `--sort`.
+            EntryTree::sort_by_attr(&mut tree, SortingAttr::Name, false);
+            let listing = structure::listing(&EntryTree::leaf_paths(&tree));
+
+            if let Some(path) = &self.snapshot_structure {
+                if let Err(error) = std::fs::write(path, &listing) {
+                    eprintln!(
+                        "error: Failed to write structure snapshot to '{}': {error}",
+                        path.display()
+                    );
+                    std::process::exit(1);
+                }
+            }
+
+            if let Some(path) = &self.check_structure {
+                let expected = std::fs::read_to_string(path).unwrap_or_else(|error| {
+                    eprintln!(
+                        "error: Failed to read structure snapshot from '{}': {error}",
+                        path.display()
+                    );
+                    std::process::exit(1);
+                });
+
+                let diff = structure::diff(&expected, &listing);
+                if !diff.is_empty() {
+                    eprintln!(
+                        "error: Benchmarks differ from structure snapshot '{}':",
+                        path.display()
+                    );
+                    for line in &diff {
+                        eprintln!("  {line}");
+                    }
+                    eprintln!(
+                        "note: Use `--snapshot-structure {}` to update the snapshot",
+                        path.display()
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
+
         // Quick exit without doing unnecessary work.
         if tree.is_empty() {
             return;
@@ -134,6 +412,31 @@ impl Divan {
         // Sorting is after filtering to compare fewer elements.
         EntryTree::sort_by_attr(&mut tree, self.sorting_attr, self.reverse_sort);
 
+        // Shuffle after sorting so that the order only depends on the seed.
+        if let Some(seed) = self.shuffle_seed.filter(|_| !action.is_list()) {
+            eprintln!("Shuffle seed: {seed}");
+            EntryTree::shuffle(&mut tree, &mut Rng::new(seed));
+        }
+
+        // Hooks are called outside of all timing, including measuring the
+        // timer and its overhead.
+        let run_hooks = |hooks: &Mutex<Vec<Hook>>| {
+            if action.is_list() {
+                return;
+            }
+
+            let hooks = mem::take(&mut *hooks.lock().unwrap_or_else(PoisonError::into_inner));
+            for hook in hooks {
+                hook();
+            }
+        };
+
+        run_hooks(&self.before_all);
+
+        // Includes calibrating the timer, so that it is attributed to overhead
+        // by `--time-breakdown`.
+        let run_start = Instant::now();
+
         let timer = match self.timer {
             TimerKind::Os => Timer::Os,
 
@@ -148,10 +451,75 @@ impl Divan {
             }
         };
 
+        // Profile settings apply to all crates by default, so Divan's debug
+        // assertions reflect how benchmarks were built.
+        if action.is_bench() && cfg!(debug_assertions) && !self.allow_debug_build {
+            eprintln!("warning: Benchmarking a debug build, so times do not reflect optimized performance; use `cargo bench` or `--release`");
+            eprintln!("note: Use `--allow-debug-build` to silence this warning");
+        }
+
+        // Raised before the stability check so that it reflects the priority.
+        let _priority_guard =
+            self.priority.filter(|_| action.is_bench()).and_then(PriorityGuard::raise);
+
         if action.is_bench() {
             eprintln!("Timer precision: {}", timer.precision());
+
+            if let Some(cpu_freq) = CpuFreq::read() {
+                eprintln!("CPU frequency: {cpu_freq}");
+
+                if !self.allow_frequency_scaling {
+                    let governors: Vec<&str> = cpu_freq.scaling_governors().collect();
+                    let is_turbo = cpu_freq.turbo == Some(true);
+
+                    if !governors.is_empty() {
+                        eprintln!("warning: CPU frequency governor is '{}' rather than 'performance', so times may vary with load", governors.join("/"));
+                    }
+
+                    if is_turbo {
+                        eprintln!("warning: CPU turbo boost is enabled, so times may vary with temperature");
+                    }
+
+                    if !governors.is_empty() || is_turbo {
+                        eprintln!("note: Pin the frequency with `cpupower frequency-set --governor performance` and disable turbo, or use `--allow-frequency-scaling` to silence this warning");
+                    }
+                }
+            }
+        }
+
+        if action.is_bench() && self.check_stability {
+            // Percentage at which a fixed workload varies too much to trust
+            // small differences between benchmarks.
+            const MAX_NOISE: f64 = 5.0;
+
+            let noise = timer.measure_noise();
+            eprintln!("Stability check: {noise:.2}% RSD");
+
+            if noise > MAX_NOISE {
+                eprintln!("warning: Machine is too noisy for reliable benchmarking ({noise:.2}% > {MAX_NOISE}% RSD of a fixed workload)");
+                eprintln!("note: Close background applications or disable CPU frequency scaling, then rerun");
+            }
         }
 
+        let columns: Vec<TreeColumn> = if action.is_bench() {
+            let mut columns = self.columns.clone().unwrap_or_else(|| TreeColumn::DEFAULT.to_vec());
+
+            // Tail percentiles follow the other time stats.
+            if self.latency {
+                for column in [TreeColumn::P99, TreeColumn::P999] {
+                    if !columns.contains(&column) {
+                        let index =
+                            columns.iter().position(|c| !c.is_time_stat()).unwrap_or(columns.len());
+                        columns.insert(index, column);
+                    }
+                }
+            }
+
+            columns
+        } else {
+            Vec::new()
+        };
+
         let shared_context = SharedContext {
             action,
             timer,
@@ -160,25 +528,186 @@ impl Divan {
             } else {
                 FineDuration::default()
             },
+            measure_setup: self.measure_setup,
+            deterministic: self.deterministic,
+            measure_cold: columns.contains(&TreeColumn::Cold),
         };
 
-        let column_widths = if action.is_bench() {
-            TreeColumn::ALL.map(|column| {
-                if column.is_last() {
-                    // The last column doesn't use padding.
-                    0
-                } else {
-                    EntryTree::common_column_width(&tree, column)
-                }
-            })
+        // Parents to hide from output, which still have full paths.
+        let strip_prefix: Vec<String> = if self.strip_common_prefix {
+            EntryTree::common_prefix(&tree)
+        } else {
+            self.strip_prefix
+                .as_deref()
+                .unwrap_or_default()
+                .split("::")
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned)
+                .collect()
+        };
+
+        let column_widths = TreeColumn::ALL.map(|column| {
+            if columns.last() == Some(&column) {
+                // The last column doesn't use padding.
+                0
+            } else if columns.contains(&column) {
+                EntryTree::common_column_width(&tree, column, &self.bench_options)
+            } else {
+                0
+            }
+        });
+
+        let tree_painter = RefCell::new(TreePainter::new(
+            EntryTree::max_stripped_name_span(&tree, &strip_prefix),
+            columns,
+            column_widths,
+            shared_context.bench_overhead,
+            self.peak_bandwidth,
+        ));
+
+        let summary = if action.is_bench()
+            && (self.summary_top.is_some()
+                || self.relative_to.is_some()
+                || self.drift_analysis
+                || self.tiers.is_some()
+                || self.stress_runs.is_some()
+                || self.time_breakdown
+                || self.geomean)
+        {
+            Some(RefCell::new(Summary::new(self.primary_stat)))
         } else {
-            [0; TreeColumn::COUNT]
+            None
+        };
+
+        let deadline = self.deadline.filter(|_| action.is_bench()).map(|deadline| DeadlineBudget {
+            end: run_start + deadline,
+            remaining_runs: Cell::new(EntryTree::leaf_paths(&tree).len()),
+            reduced_count: Cell::new(0),
+        });
+
+        {
+            let _interrupt_guard =
+                if action.is_bench() { interrupt::HandlerGuard::install() } else { None };
+
+            self.run_tree(
+                action,
+                &tree,
+                &shared_context,
+                None,
+                &strip_prefix,
+                &tree_painter,
+                summary.as_ref(),
+                deadline.as_ref(),
+            );
+        }
+
+        if let (Some(deadline), Some(budget)) = (self.deadline, &deadline) {
+            let reduced_count = budget.reduced_count.get();
+            if reduced_count > 0 {
+                let plural = if reduced_count == 1 { "" } else { "s" };
+                eprintln!("warning: Reduced sampling of {reduced_count} benchmark{plural} to fit '--deadline' of {}, so results are less precise", FineDuration::from(deadline));
+            }
+
+            let overrun = Instant::now().saturating_duration_since(budget.end);
+            if !overrun.is_zero() {
+                eprintln!(
+                    "warning: Exceeded '--deadline' by {} to collect a minimum number of samples per benchmark",
+                    FineDuration::from(overrun),
+                );
+            }
+        }
+
+        let run_time = FineDuration::from(run_start.elapsed());
+
+        if tree_painter.borrow().did_hit_resolution() {
+            eprintln!(
+                "note: Times shown as '<{}' are below the resolution of this build and CPU; consider doing more work per iteration",
+                shared_context.bench_overhead,
+            );
+        }
+
+        // Names the first path and counts the rest, e.g. "'a' and 2 others".
+        let describe_paths = |paths: &[String]| -> Option<String> {
+            let (first, rest) = paths.split_first()?;
+            Some(match rest.len() {
+                0 => format!("'{first}'"),
+                1 => format!("'{first}' and 1 other"),
+                n => format!("'{first}' and {n} others"),
+            })
         };
 
-        let tree_painter =
-            RefCell::new(TreePainter::new(EntryTree::max_name_span(&tree, 0), column_widths));
+        if let Some(paths) = describe_paths(tree_painter.borrow().time_limited_paths()) {
+            eprintln!(
+                "note: {paths} reached `max_time` before collecting all samples; consider increasing `max_time` or decreasing `sample_count`",
+            );
+        }
+
+        if let Some(paths) = describe_paths(tree_painter.borrow().overhead_bound_paths()) {
+            eprintln!(
+                "note: Samples of {paths} took under {OVERHEAD_BOUND_PRECISION_MULTIPLE}x the timer precision and may be dominated by timer overhead; consider increasing `sample_size`",
+            );
+        }
+
+        let max_allocs_exceeded = tree_painter.borrow().max_allocs_exceeded().to_vec();
+
+        for (path, allocs, max_allocs) in &max_allocs_exceeded {
+            eprintln!(
+                "error: '{path}' made up to {allocs} allocations per iteration, exceeding 'max_allocs = {max_allocs}'",
+            );
+        }
+
+        let mut missing_relative_to = false;
+
+        if let Some(summary) = summary {
+            let summary = summary.into_inner();
+            let format = SummaryFormat {
+                path_separator: self.path_separator.as_deref().unwrap_or("::"),
+                bytes_format: self.bytes_format,
+            };
+
+            if let Some(top) = self.summary_top {
+                summary.print_ranked(top, &format);
+            }
+
+            if let Some(relative_to) = &self.relative_to {
+                if !interrupt::is_interrupted() && !summary.print_relative(relative_to, &format) {
+                    eprintln!("error: No benchmark results for '{relative_to}' to compare against");
+                    missing_relative_to = true;
+                }
+            }
+
+            if self.drift_analysis {
+                summary.print_drift(&format);
+            }
+
+            if let Some(tiers) = &self.tiers {
+                summary.print_tiers(tiers, &format);
+            }
+
+            if self.stress_runs.is_some() {
+                summary.print_stress(&format);
+            }
+
+            if self.geomean && !interrupt::is_interrupted() {
+                summary.print_geomean();
+            }
+
+            if self.time_breakdown {
+                summary.print_time_breakdown(run_time);
+            }
+        }
+
+        run_hooks(&self.after_all);
+
+        if missing_relative_to || !max_allocs_exceeded.is_empty() {
+            std::process::exit(1);
+        }
 
-        self.run_tree(action, &tree, &shared_context, None, &tree_painter);
+        if interrupt::is_interrupted() {
+            eprintln!("Interrupted: skipped remaining benchmarks");
+            std::process::exit(130);
+        }
     }
 
     fn run_tree(
@@ -187,9 +716,16 @@ impl Divan {
         tree: &[EntryTree],
         shared_context: &SharedContext,
         parent_options: Option<&BenchOptions>,
+        strip_prefix: &[String],
         tree_painter: &RefCell<TreePainter>,
+        summary: Option<&RefCell<Summary>>,
+        deadline: Option<&DeadlineBudget>,
     ) {
         for (i, child) in tree.iter().enumerate() {
+            if interrupt::is_interrupted() {
+                return;
+            }
+
             let is_last = i == tree.len() - 1;
 
             let name = child.display_name();
@@ -215,12 +751,39 @@ impl Divan {
                     shared_context,
                     options,
                     tree_painter,
+                    summary,
+                    deadline,
                     is_last,
                 ),
+                EntryTree::Parent { children, .. } if child.is_stripped(strip_prefix) => {
+                    tree_painter.borrow_mut().start_hidden_parent(name);
+
+                    self.run_tree(
+                        action,
+                        children,
+                        shared_context,
+                        options,
+                        &strip_prefix[1..],
+                        tree_painter,
+                        summary,
+                        deadline,
+                    );
+
+                    tree_painter.borrow_mut().finish_hidden_parent();
+                }
                 EntryTree::Parent { children, .. } => {
                     tree_painter.borrow_mut().start_parent(name, is_last);
 
-                    self.run_tree(action, children, shared_context, options, tree_painter);
+                    self.run_tree(
+                        action,
+                        children,
+                        shared_context,
+                        options,
+                        &[],
+                        tree_painter,
+                        summary,
+                        deadline,
+                    );
 
                     tree_painter.borrow_mut().finish_parent();
                 }
@@ -236,9 +799,11 @@ impl Divan {
         shared_context: &SharedContext,
         entry_options: Option<&BenchOptions>,
         tree_painter: &RefCell<TreePainter>,
+        summary: Option<&RefCell<Summary>>,
+        deadline: Option<&DeadlineBudget>,
         is_last_entry: bool,
     ) {
-        use crate::bench::BenchContext;
+        use crate::bench::{BenchContext, PhaseTimes};
 
         let entry_display_name = bench_entry.display_name();
 
@@ -252,7 +817,56 @@ impl Divan {
             }
         };
 
+        // Runtime options scoped to a path override global ones, with the most
+        // specific path taking precedence.
+        let scoped_options: BenchOptions;
+        let options: &BenchOptions = if self.scoped_bench_options.is_empty() {
+            options
+        } else {
+            let mut path = tree_painter.borrow().current_path().join("::");
+            if !path.is_empty() {
+                path.push_str("::");
+            }
+            path.push_str(entry_display_name);
+
+            let mut matches: Vec<&(String, BenchOptions)> = self
+                .scoped_bench_options
+                .iter()
+                .filter(|(scope, _)| config::is_in_scope(&path, scope))
+                .collect();
+
+            // Stable sort to keep later arguments taking precedence on ties.
+            matches.sort_by_key(|(scope, _)| scope.len());
+
+            let mut options = options.clone();
+            for (_, scoped) in matches {
+                // Only `Copy` options can be scoped, so they are set in place.
+                options.sample_count = scoped.sample_count.or(options.sample_count);
+                options.sample_size = scoped.sample_size.or(options.sample_size);
+            }
+
+            scoped_options = options;
+            &scoped_options
+        };
+
+        // `--latency` only fills in sample counts and sizes that are not set.
+        let latency_options: BenchOptions;
+        let options: &BenchOptions = if self.latency {
+            let mut options = options.clone();
+            options.sample_count = options.sample_count.or(Some(LATENCY_SAMPLE_COUNT));
+            options.sample_size = options.sample_size.or(Some(1));
+
+            latency_options = options;
+            &latency_options
+        } else {
+            options
+        };
+
         if self.should_ignore(options.ignore.unwrap_or_default()) {
+            if let Some(deadline) = deadline {
+                deadline.skip_runs(bench_arg_names.map_or(1, <[_]>::len));
+            }
+
             tree_painter.borrow_mut().ignore_leaf(entry_display_name, is_last_entry);
             return;
         }
@@ -265,6 +879,33 @@ impl Divan {
             return;
         }
 
+        if self.deterministic && action.is_bench() {
+            let path = || {
+                let mut path = tree_painter.borrow().current_path().join("::");
+                if !path.is_empty() {
+                    path.push_str("::");
+                }
+                path.push_str(entry_display_name);
+                path
+            };
+
+            if options.sample_size.is_none() {
+                eprintln!(
+                    "error: '--deterministic' requires 'sample_size' to be set for '{}'",
+                    path()
+                );
+                std::process::exit(1);
+            }
+
+            if options.threads.as_deref().unwrap_or_default().contains(&0) {
+                eprintln!(
+                    "error: '--deterministic' does not support 'threads = 0' for '{}', which depends on available parallelism",
+                    path()
+                );
+                std::process::exit(1);
+            }
+        }
+
         let mut thread_counts: Vec<NonZeroUsize> = options
             .threads
             .as_deref()
@@ -288,6 +929,50 @@ impl Divan {
         let run_bench = |bench_display_name: &str,
                          is_last_bench: bool,
                          with_bencher: &dyn Fn(Bencher)| {
+            // Lower `max_time` to this benchmark's share of `--deadline`, split
+            // between thread counts and `--stress` runs.
+            let mut limiting_deadline: Option<&DeadlineBudget> = None;
+            let deadline_options: BenchOptions;
+            let options: &BenchOptions = match deadline {
+                Some(deadline) => {
+                    let runs = thread_counts.len() * self.stress_runs.unwrap_or(1);
+                    let share = deadline.next_run() / u32::try_from(runs).unwrap_or(u32::MAX);
+
+                    let mut options = options.clone();
+                    match options.max_time {
+                        // An explicit `max_time` stays a strict limit, so it
+                        // does not get a sample floor.
+                        Some(max_time) if max_time <= share => {}
+                        Some(_) => {
+                            options.max_time = Some(share);
+                            limiting_deadline = Some(deadline);
+                        }
+                        None => {
+                            options.max_time = Some(share);
+                            limiting_deadline = Some(deadline);
+
+                            let sample_count = options.sample_count.unwrap_or(DEFAULT_SAMPLE_COUNT);
+                            options.min_samples = options
+                                .min_samples
+                                .or(Some(sample_count.min(DEADLINE_MIN_SAMPLES)));
+                        }
+                    }
+
+                    deadline_options = options;
+                    &deadline_options
+                }
+                None => options,
+            };
+
+            if let Some(drop_first) = options.drop_first.filter(|_| action.is_bench()) {
+                let sample_count = options.sample_count.unwrap_or(DEFAULT_SAMPLE_COUNT);
+                if drop_first >= sample_count {
+                    eprintln!(
+                        "warning: Cannot drop {drop_first} of {sample_count} samples for '{bench_display_name}', keeping the last"
+                    );
+                }
+            }
+
             if has_thread_branches {
                 tree_painter.borrow_mut().start_parent(bench_display_name, is_last_bench);
             } else {
@@ -304,25 +989,115 @@ impl Divan {
                         .start_leaf(&format!("t={thread_count}"), is_last_thread_count);
                 }
 
+                let stress_runs = if action.is_bench() { self.stress_runs.unwrap_or(1) } else { 1 };
+
+                // Statistics of runs before the last for `--stress`.
+                let mut stress_stats = Vec::new();
+
+                // Time spent in each phase across all runs.
+                let mut phase_times = PhaseTimes::default();
+
                 let mut bench_context = BenchContext::new(shared_context, options, thread_count);
                 with_bencher(Bencher::new(&mut bench_context));
 
+                // Each run samples afresh to measure run-to-run reproducibility.
+                for _ in 1..stress_runs {
+                    if interrupt::is_interrupted() || !bench_context.did_run {
+                        break;
+                    }
+
+                    stress_stats.push(bench_context.compute_stats());
+                    phase_times += bench_context.phase_times;
+
+                    bench_context = BenchContext::new(shared_context, options, thread_count);
+                    with_bencher(Bencher::new(&mut bench_context));
+                }
+
+                phase_times += bench_context.phase_times;
+
+                if let Some(summary) = summary {
+                    summary.borrow_mut().add_phase_times(phase_times);
+                }
+
+                // Abandon partially-sampled benchmarks.
+                if interrupt::is_interrupted() {
+                    tree_painter.borrow_mut().finish_empty_leaf();
+                    return;
+                }
+
+                if bench_context.skipped {
+                    tree_painter.borrow_mut().finish_skipped_leaf("(skipped)");
+                    continue;
+                }
+
                 if !bench_context.did_run {
                     eprintln!(
                         "warning: No benchmark function registered for '{bench_display_name}'"
                     );
                 }
 
+                // Checked in both modes so that `--test` also enforces it.
+                if let (Some(max_allocs), true) = (options.max_allocs, bench_context.did_run) {
+                    let allocs = bench_context.max_allocs_per_iter();
+                    if allocs > max_allocs {
+                        tree_painter.borrow_mut().mark_max_allocs_exceeded(
+                            self.path_separator.as_deref().unwrap_or("::"),
+                            allocs,
+                            max_allocs,
+                        );
+                    }
+                }
+
                 let should_compute_stats =
                     bench_context.did_run && shared_context.action.is_bench();
 
-                if should_compute_stats {
+                if should_compute_stats && !bench_context.has_samples() {
+                    tree_painter.borrow_mut().finish_skipped_leaf("(no samples)");
+                } else if should_compute_stats {
                     let stats = bench_context.compute_stats();
+
+                    if bench_context.hit_max_time {
+                        if let Some(deadline) = limiting_deadline {
+                            deadline.reduced_count.set(deadline.reduced_count.get() + 1);
+                        } else {
+                            tree_painter
+                                .borrow_mut()
+                                .mark_time_limited(self.path_separator.as_deref().unwrap_or("::"));
+                        }
+                    }
+
+                    // Tuning keeps samples well above the timer precision, but
+                    // explicit sample sizes bypass it.
+                    if let Some(sample_size) = options.sample_size {
+                        let sample_size = u128::from(sample_size);
+                        let sample_picos = stats.time.median.picos.saturating_mul(sample_size);
+                        let precision = shared_context.timer.precision().picos;
+
+                        if sample_picos
+                            < precision.saturating_mul(OVERHEAD_BOUND_PRECISION_MULTIPLE)
+                        {
+                            tree_painter.borrow_mut().mark_overhead_bound(
+                                self.path_separator.as_deref().unwrap_or("::"),
+                            );
+                        }
+                    }
+
+                    let path = summary.map(|_| tree_painter.borrow().current_path().to_vec());
+
                     tree_painter.borrow_mut().finish_leaf(
                         is_last_thread_count,
                         &stats,
                         self.bytes_format,
                     );
+
+                    if let (Some(summary), Some(path)) = (summary, path) {
+                        if stress_stats.is_empty() {
+                            summary.borrow_mut().push(path, &stats);
+                        } else {
+                            stress_stats.push(stats);
+                            summary.borrow_mut().push_runs(path, &stress_stats);
+                        }
+                    }
                 } else {
                     tree_painter.borrow_mut().finish_empty_leaf();
                 }
@@ -344,6 +1119,10 @@ impl Divan {
                 let bench_arg_names = bench_arg_names.unwrap_or_default();
 
                 for (i, &arg_name) in bench_arg_names.iter().enumerate() {
+                    if interrupt::is_interrupted() {
+                        break;
+                    }
+
                     let is_last_arg = i == bench_arg_names.len() - 1;
                     let arg_index = util::slice_ptr_index(orig_arg_names, arg_name);
 
@@ -358,6 +1137,31 @@ impl Divan {
     }
 }
 
+/// Makes `Divan::filter_regex` input polymorphic.
+pub trait FilterRegex {
+    fn filter_regex(self, divan: &mut Divan);
+}
+
+impl FilterRegex for Regex {
+    fn filter_regex(self, divan: &mut Divan) {
+        divan.filters.push(Filter::Regex(self));
+    }
+}
+
+impl FilterRegex for &str {
+    #[track_caller]
+    fn filter_regex(self, divan: &mut Divan) {
+        Regex::new(self).unwrap().filter_regex(divan);
+    }
+}
+
+impl FilterRegex for String {
+    #[track_caller]
+    fn filter_regex(self, divan: &mut Divan) {
+        self.as_str().filter_regex(divan)
+    }
+}
+
 /// Makes `Divan::skip_regex` input polymorphic.
 pub trait SkipRegex {
     fn skip_regex(self, divan: &mut Divan);
@@ -383,8 +1187,43 @@ impl SkipRegex for String {
     }
 }
 
+/// Parses `value` the same way as the `--{arg}` CLI argument, for builder
+/// methods that take CLI values as strings.
+#[track_caller]
+fn parse_cli_value<T: clap::ValueEnum>(arg: &str, value: &str) -> T {
+    T::from_str(value, false).unwrap_or_else(|_| {
+        let valid: Vec<String> = T::value_variants()
+            .iter()
+            .filter_map(|variant| Some(variant.to_possible_value()?.get_name().to_owned()))
+            .collect();
+        let valid = valid.join(", ");
+        panic!("invalid value '{value}' for `--{arg}` (valid: {valid})")
+    })
+}
+
 /// Configuration options.
 impl Divan {
+    /// Creates an instance with default options, without parsing CLI arguments
+    /// or environment variables.
+    ///
+    /// Options can then be set via [builder methods](Self#options).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// divan::Divan::new()
+    ///     .sample_count(50)
+    ///     .max_time(Duration::from_secs(1))
+    ///     .filter_regex("^math::")
+    ///     .main();
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     /// Creates an instance with options set by parsing CLI arguments.
     pub fn from_args() -> Self {
         Self::default().config_with_args()
@@ -422,6 +1261,38 @@ impl Divan {
             self.skip_filters.extend(skip_filters.map(&mut parse_filter));
         }
 
+        if let Some(tags) = matches.get_many::<String>("tag") {
+            self.tags.extend(tags.cloned());
+        }
+
+        if matches.get_flag("all-tags") {
+            self.match_all_tags = true;
+        }
+
+        if let Some(path) = matches.get_one::<String>("changed-files") {
+            self = self.changed_files(path);
+        }
+
+        if matches.get_flag("allow-duplicate-names") {
+            self.allow_duplicate_names = true;
+        }
+
+        if matches.get_flag("allow-debug-build") {
+            self.allow_debug_build = true;
+        }
+
+        if matches.get_flag("allow-frequency-scaling") {
+            self.allow_frequency_scaling = true;
+        }
+
+        if matches.get_flag("check-stability") {
+            self.check_stability = true;
+        }
+
+        if let Some(&priority) = matches.get_one("priority") {
+            self.priority = Some(priority);
+        }
+
         self.action = if matches.get_flag("list") {
             Action::List
         } else if matches.get_flag("test") || !matches.get_flag("bench") {
@@ -455,12 +1326,58 @@ impl Divan {
             self.sorting_attr = sorting_attr;
         }
 
-        if let Some(&sample_count) = matches.get_one("sample-count") {
-            self.bench_options.sample_count = Some(sample_count);
+        if matches.get_flag("deterministic") {
+            self.deterministic = true;
         }
 
-        if let Some(&sample_size) = matches.get_one("sample-size") {
-            self.bench_options.sample_size = Some(sample_size);
+        if let Some(mut shuffle) = matches.get_many::<u64>("shuffle") {
+            // If the option is present without a value, then use a new seed.
+            let seed = shuffle.next().copied();
+
+            if seed.is_none() && self.deterministic {
+                eprintln!("error: '--shuffle' requires a seed when used with '--deterministic'");
+                std::process::exit(1);
+            }
+
+            self.shuffle_seed = Some(seed.unwrap_or_else(Rng::random_seed));
+        }
+
+        for sample_count in
+            matches.get_many::<ParsedScoped<u32>>("sample-count").into_iter().flatten()
+        {
+            match &sample_count.path {
+                None => self.bench_options.sample_count = Some(sample_count.value),
+                Some(path) => self.scoped_bench_options.push((
+                    path.clone(),
+                    BenchOptions {
+                        sample_count: Some(sample_count.value),
+                        ..BenchOptions::default()
+                    },
+                )),
+            }
+        }
+
+        for sample_size in
+            matches.get_many::<ParsedScoped<u32>>("sample-size").into_iter().flatten()
+        {
+            match &sample_size.path {
+                None => self.bench_options.sample_size = Some(sample_size.value),
+                Some(path) => self.scoped_bench_options.push((
+                    path.clone(),
+                    BenchOptions {
+                        sample_size: Some(sample_size.value),
+                        ..BenchOptions::default()
+                    },
+                )),
+            }
+        }
+
+        if matches.get_flag("latency") {
+            self.latency = true;
+        }
+
+        if let Some(&drop_first) = matches.get_one("drop-first") {
+            self.bench_options.drop_first = Some(drop_first);
         }
 
         if let Some(thread_counts) = matches.get_many::<usize>("threads") {
@@ -478,12 +1395,24 @@ impl Divan {
             self.bench_options.max_time = Some(max_time);
         }
 
+        if let Some(&ParsedDuration(deadline)) = matches.get_one("deadline") {
+            self.deadline = Some(deadline);
+        }
+
+        if let Some(&min_samples) = matches.get_one("min-samples") {
+            self.bench_options.min_samples = Some(min_samples);
+        }
+
         if let Some(mut skip_ext_time) = matches.get_many::<bool>("skip-ext-time") {
             // If the option is present without a value, then it's `true`.
             self.bench_options.skip_ext_time =
                 Some(matches!(skip_ext_time.next(), Some(true) | None));
         }
 
+        if matches.get_flag("measure-setup") {
+            self.measure_setup = true;
+        }
+
         if let Some(&count) = matches.get_one::<MaxCountUInt>("items-count") {
             self.counter_mut(ItemsCount::new(count));
         }
@@ -492,10 +1421,93 @@ impl Divan {
             self.counter_mut(BytesCount::new(count));
         }
 
+        if let Some(&top) = matches.get_one::<usize>("summary-top") {
+            self.summary_top = Some(top);
+        } else if matches.get_flag("summary") {
+            self.summary_top = Some(DEFAULT_SUMMARY_TOP);
+        }
+
+        if let Some(relative_to) = matches.get_one::<String>("relative-to") {
+            self.relative_to = Some(relative_to.clone());
+        }
+
+        if let Some(&primary_stat) = matches.get_one("primary-stat") {
+            self.primary_stat = primary_stat;
+        }
+
+        if let Some(names) = matches.get_many::<String>("columns") {
+            let columns: Vec<TreeColumn> = names
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    TreeColumn::from_name(name).unwrap_or_else(|error| {
+                        command.error(clap::error::ErrorKind::ValueValidation, error).exit();
+                    })
+                })
+                .collect();
+
+            if !columns.is_empty() {
+                self.columns = Some(columns);
+            }
+        }
+
+        for (flag, column) in [
+            ("show-iqr", TreeColumn::Iqr),
+            ("show-mad", TreeColumn::Mad),
+            ("show-cold", TreeColumn::Cold),
+        ] {
+            if matches.get_flag(flag) {
+                self.show_column(column, true);
+            }
+        }
+
+        if matches.get_flag("drift-analysis") {
+            self.drift_analysis = true;
+        }
+
+        if let Some(bounds) = matches.get_many::<ParsedDuration>("tiers") {
+            self = self.tiers(bounds.map(|bound| bound.0));
+        }
+
+        if let Some(&runs) = matches.get_one::<usize>("stress") {
+            self = self.stress(runs);
+        }
+
+        if matches.get_flag("time-breakdown") {
+            self.time_breakdown = true;
+        }
+
+        if matches.get_flag("geomean") {
+            self.geomean = true;
+        }
+
+        if let Some(path) = matches.get_one::<String>("snapshot-structure") {
+            self = self.snapshot_structure(path);
+        }
+
+        if let Some(path) = matches.get_one::<String>("check-structure") {
+            self = self.check_structure(path);
+        }
+
+        if let Some(path_separator) = matches.get_one::<String>("path-separator") {
+            self.path_separator = Some(path_separator.clone());
+        }
+
+        if let Some(prefix) = matches.get_one::<String>("strip-prefix") {
+            self = self.strip_prefix(prefix);
+        }
+
+        if matches.get_flag("strip-common-prefix") {
+            self.strip_common_prefix = true;
+        }
+
         if let Some(&PrivBytesFormat(bytes_format)) = matches.get_one("bytes-format") {
             self.bytes_format = bytes_format;
         }
 
+        if let Some(&ParsedBandwidth(peak_bandwidth)) = matches.get_one("peak-bandwidth") {
+            self.peak_bandwidth = Some(peak_bandwidth);
+        }
+
         if let Some(&count) = matches.get_one::<MaxCountUInt>("chars-count") {
             self.counter_mut(CharsCount::new(count));
         }
@@ -517,6 +1529,318 @@ impl Divan {
         self
     }
 
+    /// Prints ranked lists of the `top` fastest and slowest benchmarks by
+    /// median time after running. [`primary_stat`](Self::primary_stat) selects
+    /// another statistic.
+    ///
+    /// If benchmarks have [counters](crate::counter), lists are also printed
+    /// for the highest and lowest throughput of each counter.
+    ///
+    /// This option is equivalent to the `--summary-top` CLI argument, or
+    /// `--summary` for a default of 5.
+    #[must_use]
+    pub fn summary(mut self, top: usize) -> Self {
+        self.summary_top = Some(top);
+        self
+    }
+
+    /// Sets which columns to show next to the benchmark tree, in order.
+    ///
+    /// Column names are `fastest` (or `min`), `slowest` (or `max`), `median`,
+    /// `mean`, `samples`, `iters`, `iqr`, `mad`, `stddev`, `p99`, `p99.9`, and
+    /// `cold`. If `names` is empty, the default columns are shown.
+    ///
+    /// This option is equivalent to the `--columns` CLI argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let divan = divan::Divan::default().columns(["median", "stddev", "samples"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any name is not a valid column.
+    #[must_use]
+    #[track_caller]
+    pub fn columns<I>(mut self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let columns: Vec<TreeColumn> = names
+            .into_iter()
+            .map(|name| {
+                TreeColumn::from_name(name.as_ref()).unwrap_or_else(|error| panic!("{error}"))
+            })
+            .collect();
+
+        self.columns = if columns.is_empty() { None } else { Some(columns) };
+        self
+    }
+
+    /// Shows the interquartile range (IQR) of times as a column, after the
+    /// other time columns.
+    ///
+    /// This option is equivalent to the `--show-iqr` CLI argument.
+    #[must_use]
+    pub fn show_iqr(mut self, show: bool) -> Self {
+        self.show_column(TreeColumn::Iqr, show);
+        self
+    }
+
+    /// Shows the median absolute deviation (MAD) of times as a column, after
+    /// the other time columns.
+    ///
+    /// This option is equivalent to the `--show-mad` CLI argument.
+    #[must_use]
+    pub fn show_mad(mut self, show: bool) -> Self {
+        self.show_column(TreeColumn::Mad, show);
+        self
+    }
+
+    /// Shows the time of the first call of each benchmark as a `cold` column,
+    /// after the other time columns.
+    ///
+    /// The first call is measured before sample size tuning and sampling, such
+    /// as to see the cost of lazy initialization. This is a single observation,
+    /// so it is inherently noisy.
+    ///
+    /// This option is equivalent to the `--show-cold` CLI argument.
+    #[must_use]
+    pub fn show_cold(mut self, show: bool) -> Self {
+        self.show_column(TreeColumn::Cold, show);
+        self
+    }
+
+    /// Adds or removes `column` for `--show-*` options.
+    fn show_column(&mut self, column: TreeColumn, show: bool) {
+        let columns = self.columns.get_or_insert_with(|| TreeColumn::DEFAULT.to_vec());
+
+        if !show {
+            columns.retain(|&c| c != column);
+            return;
+        }
+
+        // Spread follows the other time stats.
+        if !columns.contains(&column) {
+            let index = columns.iter().position(|c| !c.is_time_stat()).unwrap_or(columns.len());
+            columns.insert(index, column);
+        }
+    }
+
+    /// Sets the statistic that [`summary`](Self::summary),
+    /// [`relative_to`](Self::relative_to), [`tiers`](Self::tiers),
+    /// [`stress`](Self::stress), and [`geomean`](Self::geomean) compare
+    /// benchmarks by. `stat` is one of:
+    ///
+    /// - `"median"` (the default) is robust to outliers, but ignores the values
+    ///   of most samples.
+    /// - `"mean"` uses every sample, but is skewed by outliers such as from
+    ///   preemption.
+    /// - `"trimmed-mean"` excludes the fastest and slowest 10% of samples
+    ///   before averaging the rest.
+    /// - `"fastest"` (or `"min"`) best isolates uninterrupted execution, but is
+    ///   biased towards lucky outliers and is less reproducible between runs.
+    ///
+    /// This option is equivalent to the `--primary-stat` CLI argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stat` is not a valid `--primary-stat` value.
+    #[must_use]
+    #[track_caller]
+    pub fn primary_stat(mut self, stat: &str) -> Self {
+        self.primary_stat = parse_cli_value("primary-stat", stat);
+        self
+    }
+
+    /// Prints the median time of each benchmark relative to the benchmark at
+    /// `path` after running, such as `1.83x` for being 83% slower.
+    /// [`primary_stat`](Self::primary_stat) selects another statistic.
+    ///
+    /// `path` is the full `::`-separated path of the benchmark, including any
+    /// argument or thread count, such as `"math::fibonacci::10"`. If no
+    /// benchmark was run at `path`, Divan exits with an error.
+    ///
+    /// This option is equivalent to the `--relative-to` CLI argument.
+    #[must_use]
+    pub fn relative_to(mut self, path: impl Into<String>) -> Self {
+        self.relative_to = Some(path.into());
+        self
+    }
+
+    /// Prints the median time of the early, middle, and late thirds of each
+    /// benchmark's samples after running, along with the change from early to
+    /// late samples.
+    ///
+    /// This distinguishes steady benchmarks from ones whose environment changes
+    /// during the run, such as from thermal throttling. Benchmarks are flagged
+    /// as drifting if their times consistently increase or decrease by at least
+    /// 5%.
+    ///
+    /// This requires at least 30 samples per benchmark to be meaningful, so
+    /// benchmarks with fewer samples are not listed.
+    ///
+    /// This option is equivalent to the `--drift-analysis` CLI argument.
+    #[must_use]
+    pub fn drift_analysis(mut self, analyze: bool) -> Self {
+        self.drift_analysis = analyze;
+        self
+    }
+
+    /// Prints benchmarks grouped into tiers of median time after running, such
+    /// as for an overview of which operations in a crate are fast or slow.
+    /// [`primary_stat`](Self::primary_stat) selects another statistic.
+    ///
+    /// Tiers are separated by `bounds`. For example, bounds of 100ns and 1ms
+    /// make tiers for under 100ns, 100ns to 1ms, and 1ms and over.
+    ///
+    /// This option is equivalent to the `--tiers` CLI argument, where bounds
+    /// are comma-separated like `100ns,1ms` (the default if no bounds are
+    /// given).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let divan = divan::Divan::default()
+    ///     .tiers([Duration::from_nanos(100), Duration::from_millis(1)]);
+    /// ```
+    #[must_use]
+    pub fn tiers(mut self, bounds: impl IntoIterator<Item = Duration>) -> Self {
+        let mut bounds: Vec<FineDuration> = bounds.into_iter().map(FineDuration::from).collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        self.tiers = Some(bounds);
+        self
+    }
+
+    /// Runs each benchmark `runs` separate times, each with fresh sampling,
+    /// and prints the spread of their median times after running.
+    /// [`primary_stat`](Self::primary_stat) selects another statistic.
+    ///
+    /// This measures run-to-run reproducibility rather than the variance
+    /// between samples, which is useful for debugging benchmarks whose results
+    /// differ wildly between runs. The spread is led by the relative standard
+    /// deviation (RSD) across runs. The tree shows the results of the last run.
+    ///
+    /// Since every benchmark is repeated, this is intended to be combined with
+    /// a filter for a single benchmark, such as [`Divan::filter_exact`].
+    /// Values below 2 disable repeated runs.
+    ///
+    /// This option is equivalent to the `--stress` CLI argument.
+    #[must_use]
+    pub fn stress(mut self, runs: usize) -> Self {
+        self.stress_runs = Some(runs).filter(|&runs| runs > 1);
+        self
+    }
+
+    /// Prints how the total time of the run was split between sampling,
+    /// sample size tuning (warmup), `--measure-setup`, and overhead after
+    /// running.
+    ///
+    /// This helps decide where to cut when benchmark runs take too long. For
+    /// example, a large warmup share suggests setting
+    /// [`sample_size`](Self::sample_size) to skip tuning, and a large overhead
+    /// share suggests expensive work outside of benchmark loops, such as
+    /// creating benchmark arguments.
+    ///
+    /// This option is equivalent to the `--time-breakdown` CLI argument.
+    #[must_use]
+    pub fn time_breakdown(mut self, breakdown: bool) -> Self {
+        self.time_breakdown = breakdown;
+        self
+    }
+
+    /// Prints the geometric mean of median times across all benchmarks after
+    /// running, as a single headline figure such as for dashboards.
+    /// [`primary_stat`](Self::primary_stat) selects another statistic.
+    ///
+    /// Unlike the arithmetic mean, the geometric mean is not dominated by the
+    /// slowest benchmarks: making any benchmark 2x faster lowers it by the same
+    /// factor.
+    ///
+    /// This option is equivalent to the `--geomean` CLI argument.
+    #[must_use]
+    pub fn geomean(mut self, geomean: bool) -> Self {
+        self.geomean = geomean;
+        self
+    }
+
+    /// Writes a listing of all benchmarks to `path` before running, without
+    /// any timings, for checking later via [`Divan::check_structure`].
+    ///
+    /// The listing has the full path of each benchmark on its own line,
+    /// including generic types, constants, and `args` values, sorted by name.
+    /// Only benchmarks selected by filters are listed.
+    ///
+    /// This option is equivalent to the `--snapshot-structure` CLI argument.
+    #[must_use]
+    pub fn snapshot_structure(mut self, path: impl Into<PathBuf>) -> Self {
+        self.snapshot_structure = Some(path.into());
+        self
+    }
+
+    /// Compares the benchmarks against a listing written by
+    /// [`Divan::snapshot_structure`] before running, and exits with an error
+    /// if any were added, removed, renamed, or reordered.
+    ///
+    /// This catches changes to the set of benchmarks in CI separately from
+    /// changes in performance. It is commonly combined with `--list` or
+    /// `--test` to skip benchmarking.
+    ///
+    /// This option is equivalent to the `--check-structure` CLI argument.
+    #[must_use]
+    pub fn check_structure(mut self, path: impl Into<PathBuf>) -> Self {
+        self.check_structure = Some(path.into());
+        self
+    }
+
+    /// Sets the separator used between path components when displaying full
+    /// benchmark paths, such as in [`Divan::summary`] and
+    /// [`Divan::relative_to`]. The default is `::`.
+    ///
+    /// This only affects output. Filters such as [`Divan::filter_exact`] and
+    /// [`Divan::skip_regex`] still match against the original `::`-separated
+    /// path.
+    ///
+    /// This option is equivalent to the `--path-separator` CLI argument.
+    #[must_use]
+    pub fn path_separator(mut self, separator: impl Into<String>) -> Self {
+        self.path_separator = Some(separator.into());
+        self
+    }
+
+    /// Hides the leading groups of `prefix`, such as `my_crate::benchmarks`,
+    /// from the benchmark tree so that their contents are shown at the top
+    /// level.
+    ///
+    /// A group is only hidden if it directly contains no benchmarks, since
+    /// those would otherwise have no group heading. Filters, path-scoped
+    /// options, and full paths shown elsewhere, such as in [`Divan::summary`]
+    /// and [`Divan::relative_to`], still use full paths so that benchmarks with
+    /// the same name under different prefixes do not collide.
+    ///
+    /// This option is equivalent to the `--strip-prefix` CLI argument.
+    #[must_use]
+    pub fn strip_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.strip_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Hides the longest chain of groups shared by all benchmarks, as if it
+    /// were passed to [`Divan::strip_prefix`].
+    ///
+    /// This option is equivalent to the `--strip-common-prefix` CLI argument.
+    #[must_use]
+    pub fn strip_common_prefix(mut self, strip: bool) -> Self {
+        self.strip_common_prefix = strip;
+        self
+    }
+
     /// Also run benchmarks marked [`#[ignore]`](https://doc.rust-lang.org/reference/attributes/testing.html#the-ignore-attribute).
     ///
     /// This option is equivalent to the `--include-ignored` CLI argument.
@@ -535,11 +1859,146 @@ impl Divan {
         self
     }
 
+    /// Only uses explicit sample counts and sizes so that runs on different
+    /// machines produce structurally identical output, differing only in
+    /// measured values.
+    ///
+    /// This disables tuning [`sample_size`](macro@crate::bench#sample_size) to
+    /// machine speed and ignores [`min_time`](macro@crate::bench#min_time)
+    /// and [`max_time`](macro@crate::bench#max_time). Divan exits with an
+    /// error if a benchmark has no `sample_size` set, or if it uses
+    /// [`threads = 0`](macro@crate::bench#threads) for available parallelism.
+    /// The CLI also requires `--shuffle` to be given a seed.
+    ///
+    /// This is useful for snapshot testing and reproducible CI artifacts.
+    ///
+    /// This option is equivalent to the `--deterministic` CLI argument.
+    #[must_use]
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Runs benchmarks in a random order determined by `seed`, which helps to
+    /// detect results that depend on the order benchmarks run in, such as from
+    /// CPU caches or heat.
+    ///
+    /// Benchmarks are shuffled within each module and then reported in the
+    /// order they run. The seed is printed so that a suspicious run can be
+    /// reproduced.
+    ///
+    /// This option is equivalent to the `--shuffle <SEED>` CLI argument. If
+    /// `--shuffle` is passed without a seed, a new seed is used each run.
+    #[must_use]
+    pub fn shuffle(mut self, seed: u64) -> Self {
+        self.shuffle_seed = Some(seed);
+        self
+    }
+
+    /// Sorts benchmarks by `attr`, which is one of `"kind"` (the default),
+    /// `"name"`, or `"location"`. If `reverse` is `true`, benchmarks are sorted
+    /// in descending order.
+    ///
+    /// This option is equivalent to the `--sort` CLI argument, or `--sortr` if
+    /// `reverse` is `true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `attr` is not a valid `--sort` value.
+    #[must_use]
+    #[track_caller]
+    pub fn sort_by(mut self, attr: &str, reverse: bool) -> Self {
+        self.sorting_attr = parse_cli_value("sort", attr);
+        self.reverse_sort = reverse;
+        self
+    }
+
+    /// Only runs benchmarks that match `filter` as a regular expression
+    /// pattern.
+    ///
+    /// This option is equivalent to the positional `filter` CLI argument,
+    /// without `--exact`.
+    ///
+    /// If called after [`Divan::from_args`], this adds to any filters passed
+    /// via the CLI. A benchmark runs if it matches any filter, including those
+    /// from [`Divan::filter_exact`], and does not match any skip filter.
+    ///
+    /// # Examples
+    ///
+    /// This method is commonly used with a [`&str`](prim@str) or [`String`]:
+    ///
+    /// ```
+    /// # use divan::Divan;
+    /// let filter = "(add|sub)";
+    /// let divan = Divan::default().filter_regex(filter);
+    /// ```
+    ///
+    /// A pre-built [`Regex`] can also be provided:
+    ///
+    /// ```
+    /// # use divan::Divan;
+    /// let filter = regex::Regex::new("(add|sub)").unwrap();
+    /// let divan = Divan::default().filter_regex(filter);
+    /// ```
+    ///
+    /// Calling this repeatedly will add multiple filters:
+    ///
+    /// ```
+    /// # use divan::Divan;
+    /// let divan = Divan::default()
+    ///     .filter_regex("(add|sub)")
+    ///     .filter_regex("collections.*default");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `filter` is a string and [`Regex::new`] fails.
+    #[must_use]
+    pub fn filter_regex(mut self, filter: impl FilterRegex) -> Self {
+        filter.filter_regex(&mut self);
+        self
+    }
+
+    /// Only runs benchmarks that exactly match `filter`.
+    ///
+    /// This option is equivalent to the positional `filter` CLI argument with
+    /// `--exact`.
+    ///
+    /// If called after [`Divan::from_args`], this adds to any filters passed
+    /// via the CLI.
+    ///
+    /// # Examples
+    ///
+    /// This method is commonly used with a [`&str`](prim@str) or [`String`]:
+    ///
+    /// ```
+    /// # use divan::Divan;
+    /// let filter = "arithmetic::add";
+    /// let divan = Divan::default().filter_exact(filter);
+    /// ```
+    ///
+    /// Calling this repeatedly will add multiple filters:
+    ///
+    /// ```
+    /// # use divan::Divan;
+    /// let divan = Divan::default()
+    ///     .filter_exact("arithmetic::add")
+    ///     .filter_exact("collections::vec::default");
+    /// ```
+    #[must_use]
+    pub fn filter_exact(mut self, filter: impl Into<String>) -> Self {
+        self.filters.push(Filter::Exact(filter.into()));
+        self
+    }
+
     /// Skips benchmarks that match `filter` as a regular expression pattern.
     ///
     /// This option is equivalent to the `--skip filter` CLI argument, without
     /// `--exact`.
     ///
+    /// If called after [`Divan::from_args`], this adds to any skip filters
+    /// passed via the CLI. Skip filters take precedence over other filters.
+    ///
     /// # Examples
     ///
     /// This method is commonly used with a [`&str`](prim@str) or [`String`]:
@@ -604,6 +2063,176 @@ impl Divan {
         self
     }
 
+    /// Only runs benchmarks with the [`tags`](macro@crate::bench#tags) option
+    /// set to include `tag`.
+    ///
+    /// This option is equivalent to the `--tag` CLI argument. Calling this
+    /// repeatedly runs benchmarks that have any of the tags, or all of them if
+    /// [`Divan::match_all_tags`] is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divan::Divan;
+    /// let divan = Divan::default().tag("simd").tag("hot");
+    /// ```
+    #[must_use]
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Only runs benchmarks that have all tags passed to [`Divan::tag`],
+    /// rather than any.
+    ///
+    /// This option is equivalent to the `--all-tags` CLI argument.
+    #[must_use]
+    pub fn match_all_tags(mut self) -> Self {
+        self.match_all_tags = true;
+        self
+    }
+
+    /// Only runs benchmarks defined in source files listed in the file at
+    /// `path`, such as the output of `git diff --name-only main` in CI.
+    ///
+    /// The file has one path per line, and blank lines are ignored. Listed
+    /// paths may be relative to a different directory than the paths recorded
+    /// by `file!()`, which are usually relative to the workspace root, or be
+    /// absolute. A benchmark is selected if the shorter of its path and a
+    /// listed path is a suffix of the other at a `/` or `\` boundary, so
+    /// `crate/benches/math.rs` and `benches/math.rs` match, but `s/math.rs`
+    /// does not.
+    ///
+    /// This selection applies in addition to name filters and tags.
+    ///
+    /// This option is equivalent to the `--changed-files` CLI argument.
+    #[must_use]
+    pub fn changed_files(mut self, path: impl Into<PathBuf>) -> Self {
+        self.changed_files = Some(path.into());
+        self
+    }
+
+    /// Calls `f` once before running any benchmarks.
+    ///
+    /// This is useful for process-wide setup, such as initializing a logger or
+    /// printing a custom header. It is called after benchmarks are selected and
+    /// before measuring the timer, so it does not affect any timing. Multiple
+    /// functions are called in the order they were added.
+    ///
+    /// This is not called if no benchmarks are selected or when listing
+    /// benchmarks via `--list`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// divan::Divan::from_args()
+    ///     .before_all(|| println!("Starting benchmarks"))
+    ///     .after_all(|| println!("Finished benchmarks"))
+    ///     .main();
+    /// ```
+    #[must_use]
+    pub fn before_all(self, f: impl FnOnce() + Send + 'static) -> Self {
+        self.before_all.lock().unwrap_or_else(PoisonError::into_inner).push(Box::new(f));
+        self
+    }
+
+    /// Calls `f` once after running all benchmarks.
+    ///
+    /// This is the counterpart to [`Divan::before_all`], such as for tearing
+    /// down global resources or printing a custom footer. It is called after
+    /// all results and summaries are printed, including when the run is
+    /// interrupted via Ctrl-C with the `ctrl_c` crate feature.
+    #[must_use]
+    pub fn after_all(self, f: impl FnOnce() + Send + 'static) -> Self {
+        self.after_all.lock().unwrap_or_else(PoisonError::into_inner).push(Box::new(f));
+        self
+    }
+
+    /// Runs benchmarks even if multiple have the same path.
+    ///
+    /// By default, Divan exits with an error if multiple benchmarks have the
+    /// same path, such as when using the same
+    /// [`name`](macro@crate::bench#name) twice in a module. This makes their
+    /// results indistinguishable.
+    ///
+    /// This option is equivalent to the `--allow-duplicate-names` CLI
+    /// argument.
+    #[must_use]
+    pub fn allow_duplicate_names(mut self) -> Self {
+        self.allow_duplicate_names = true;
+        self
+    }
+
+    /// Benchmarks debug builds without warning.
+    ///
+    /// By default, Divan warns when benchmarking a build with debug assertions,
+    /// such as from `cargo run` without `--release`, since its times are not
+    /// representative of optimized code. This is for intentionally measuring
+    /// debug performance.
+    ///
+    /// This option is equivalent to the `--allow-debug-build` CLI argument.
+    #[must_use]
+    pub fn allow_debug_build(mut self) -> Self {
+        self.allow_debug_build = true;
+        self
+    }
+
+    /// Benchmarks without warning about CPU frequency scaling.
+    ///
+    /// By default on Linux, Divan prints the CPU frequency governor and whether
+    /// turbo boost is enabled, and warns if either lets the frequency vary,
+    /// since times may then change with load and temperature. Pinning the
+    /// frequency requires root, such as via
+    /// `cpupower frequency-set --governor performance` and writing `1` to
+    /// `/sys/devices/system/cpu/intel_pstate/no_turbo` or `0` to
+    /// `/sys/devices/system/cpu/cpufreq/boost`.
+    ///
+    /// This check is advisory only. It reads
+    /// `/sys/devices/system/cpu/cpu*/cpufreq/scaling_governor` and the above
+    /// turbo files, and never modifies system state.
+    ///
+    /// This option is equivalent to the `--allow-frequency-scaling` CLI
+    /// argument.
+    #[must_use]
+    pub fn allow_frequency_scaling(mut self) -> Self {
+        self.allow_frequency_scaling = true;
+        self
+    }
+
+    /// Checks whether the machine is currently quiet enough for reliable
+    /// benchmarking before running any benchmarks.
+    ///
+    /// This repeatedly times a fixed workload, which should take the same time
+    /// on every run. If the relative standard deviation of its times exceeds
+    /// 5%, Divan warns that the machine is too noisy, such as from background
+    /// processes, before spending a long run on unreliable results.
+    ///
+    /// This option is equivalent to the `--check-stability` CLI argument.
+    #[must_use]
+    pub fn check_stability(mut self, check: bool) -> Self {
+        self.check_stability = check;
+        self
+    }
+
+    /// Raises the scheduling priority of benchmarks to reduce noise from other
+    /// processes, where `priority` is `"high"` or `"realtime"`.
+    ///
+    /// This requires the `priority` crate feature. If the priority cannot be
+    /// raised, Divan warns and runs benchmarks at normal priority. See
+    /// [options](Self#options) for the risks of realtime priority.
+    ///
+    /// This option is equivalent to the `--priority` CLI argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is not a valid `--priority` value.
+    #[must_use]
+    #[track_caller]
+    pub fn priority(mut self, priority: &str) -> Self {
+        self.priority = Some(parse_cli_value("priority", priority));
+        self
+    }
+
     /// Sets the number of sampling iterations.
     ///
     /// This option is equivalent to the `--sample-count` CLI argument.
@@ -618,6 +2247,25 @@ impl Divan {
         self
     }
 
+    /// Sets the timer used for measuring samples, which is `"os"` (the
+    /// default) for the operating system's timer or `"tsc"` for the CPU
+    /// timestamp counter.
+    ///
+    /// If the timestamp counter is unavailable, Divan warns and falls back to
+    /// the operating system's timer.
+    ///
+    /// This option is equivalent to the `--timer` CLI argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timer` is not a valid `--timer` value.
+    #[must_use]
+    #[track_caller]
+    pub fn timer(mut self, timer: &str) -> Self {
+        self.timer = parse_cli_value("timer", timer);
+        self
+    }
+
     /// Sets the number of iterations inside a single sample.
     ///
     /// This option is equivalent to the `--sample-size` CLI argument.
@@ -627,6 +2275,46 @@ impl Divan {
         self
     }
 
+    /// Samples for tail latency rather than throughput, and shows the 99th
+    /// and 99.9th percentile times as `p99` and `p99.9` columns.
+    ///
+    /// By default, Divan tunes each sample to run many iterations, which
+    /// averages away the latency of individual slow iterations. This instead
+    /// defaults [`sample_size`](Self::sample_size) to 1 and
+    /// [`sample_count`](Self::sample_count) to 10,000, so that each sample is
+    /// a single operation and the tail has enough samples to be estimated.
+    /// Sample counts and sizes set explicitly, such as via
+    /// [`#[divan::bench]`](macro@crate::bench) options, take precedence.
+    ///
+    /// This tradeoff has costs:
+    ///
+    /// - Timer overhead is paid for every iteration rather than amortized over
+    ///   a sample, so operations near the timer precision cannot be measured
+    ///   accurately.
+    ///
+    /// - Many samples take longer to collect and may stop at
+    ///   [`max_time`](Self::max_time).
+    ///
+    /// This option is equivalent to the `--latency` CLI argument.
+    #[must_use]
+    pub fn latency(mut self, latency: bool) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Discards the first `count` samples from statistics.
+    ///
+    /// Unlike warmup, these samples are timed and count towards
+    /// [`sample_count`](Self::sample_count). This is useful for excluding
+    /// outliers from one-time costs like page faults on first touch.
+    ///
+    /// This option is equivalent to the `--drop-first` CLI argument.
+    #[inline]
+    pub fn drop_first(mut self, count: u32) -> Self {
+        self.bench_options.drop_first = Some(count);
+        self
+    }
+
     /// Run across multiple threads.
     ///
     /// This enables you to measure contention on [atomics and
@@ -667,6 +2355,43 @@ impl Divan {
         self
     }
 
+    /// Sets a time budget for the entire run, including benchmarks and Divan's
+    /// own setup.
+    ///
+    /// Before each benchmark runs, the remaining time is split evenly between
+    /// the benchmarks that have yet to run, and the benchmark's
+    /// [`max_time`](Self::max_time) is lowered to its share. Benchmarks that
+    /// finish early leave more time for later ones. If any benchmark reaches its
+    /// share before collecting all samples, a warning is printed after running
+    /// since its results are less precise.
+    ///
+    /// Sampling is never reduced below 10 samples per benchmark, or
+    /// [`min_samples`](Self::min_samples) if set, so the run may exceed the
+    /// deadline if benchmarks are too slow. This is also printed as a warning.
+    /// Benchmarks with an explicit [`max_time`](macro@crate::bench#max_time)
+    /// keep it as a strict limit without this floor, since it may allow fewer
+    /// samples.
+    ///
+    /// The deadline has no effect with [`deterministic`](Self::deterministic),
+    /// which ignores `max_time`.
+    ///
+    /// This option is equivalent to the `--deadline` CLI argument.
+    #[must_use]
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the minimum number of samples to collect, even if doing so exceeds
+    /// [`max_time`](Self::max_time).
+    ///
+    /// This option is equivalent to the `--min-samples` CLI argument.
+    #[inline]
+    pub fn min_samples(mut self, count: u32) -> Self {
+        self.bench_options.min_samples = Some(count);
+        self
+    }
+
     /// When accounting for `min_time` or `max_time`, skip time external to
     /// benchmarked functions.
     ///
@@ -676,6 +2401,21 @@ impl Divan {
         self.bench_options.skip_ext_time = Some(skip);
         self
     }
+
+    /// Separately measures time spent generating inputs via
+    /// [`Bencher::with_inputs`](crate::Bencher::with_inputs), which is then
+    /// reported below the benchmark's time.
+    ///
+    /// This helps to judge whether a benchmark is dominated by setup. Because
+    /// it samples the input generator in its own loop, it roughly doubles the
+    /// time spent benchmarking functions that use inputs.
+    ///
+    /// This option is equivalent to the `--measure-setup` CLI argument.
+    #[inline]
+    pub fn measure_setup(mut self, measure: bool) -> Self {
+        self.measure_setup = measure;
+        self
+    }
 }
 
 /// Use [`Counter`s](crate::counter::Counter) to get throughput across all
@@ -722,6 +2462,35 @@ impl Divan {
         self
     }
 
+    /// Shows [`BytesCount`] throughput as a percentage of the peak memory
+    /// bandwidth of the machine, in bytes per second.
+    ///
+    /// This puts memory-bound benchmarks in context, such as seeing that a copy
+    /// runs at 80% of what the hardware can do. Benchmarks without a
+    /// [`BytesCount`] are unaffected.
+    ///
+    /// This option is equivalent to the `--peak-bandwidth` CLI argument or
+    /// `DIVAN_PEAK_BANDWIDTH` environment variable, which accepts rates like
+    /// `25GB/s` or `12.8GiB/s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // 25 GB/s:
+    /// let divan = divan::Divan::default().peak_bandwidth(25_000_000_000);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_per_sec` is 0, which `--peak-bandwidth` also rejects.
+    #[must_use]
+    #[track_caller]
+    pub fn peak_bandwidth(mut self, bytes_per_sec: u64) -> Self {
+        assert!(bytes_per_sec > 0, "peak bandwidth must be greater than 0");
+        self.peak_bandwidth = Some(bytes_per_sec as f64);
+        self
+    }
+
     /// Sets the number of bytes processed.
     ///
     /// This option is equivalent to the `--chars-count` CLI argument or
@@ -731,3 +2500,161 @@ impl Divan {
         self.counter(count.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_exact() {
+        let paths = ["math::add", "math::add_assign", "other::math::add"];
+
+        let matched = |divan: Divan| -> Vec<&str> {
+            paths.iter().copied().filter(|path| divan.filter(path)).collect()
+        };
+
+        // Regex filters match substrings.
+        assert_eq!(matched(Divan::new().filter_regex("math::add")), paths);
+
+        // Exact filters match whole paths.
+        assert_eq!(matched(Divan::new().filter_exact("math::add")), ["math::add"]);
+
+        // Multiple exact filters match any of them.
+        assert_eq!(
+            matched(Divan::new().filter_exact("math::add").filter_exact("other::math::add")),
+            ["math::add", "other::math::add"],
+        );
+
+        // Prefixes do not match exactly.
+        assert!(matched(Divan::new().filter_exact("math")).is_empty());
+
+        // Skips apply the same matching.
+        assert_eq!(
+            matched(Divan::new().skip_exact("math::add")),
+            ["math::add_assign", "other::math::add"],
+        );
+    }
+
+    /// Tests that builders accept the same values as the CLI.
+    #[test]
+    fn cli_value_builders() {
+        let divan = Divan::new().sort_by("name", true).timer("tsc").primary_stat("min");
+        assert!(matches!(divan.sorting_attr, SortingAttr::Name));
+        assert!(divan.reverse_sort);
+        assert!(matches!(divan.timer, TimerKind::Tsc));
+        assert!(matches!(divan.primary_stat, PrimaryStat::Fastest));
+
+        let divan = Divan::new().priority("realtime");
+        assert!(divan.priority == Some(Priority::Realtime));
+
+        let divan = Divan::new().columns(["min", "max", "stddev"]);
+        let columns = [TreeColumn::Fastest, TreeColumn::Slowest, TreeColumn::StdDev];
+        assert!(divan.columns.as_deref() == Some(&columns[..]));
+
+        let divan = Divan::new().columns(["median", "samples"]).show_mad(true).show_iqr(true);
+        let columns = [TreeColumn::Median, TreeColumn::Mad, TreeColumn::Iqr, TreeColumn::Samples];
+        assert!(divan.columns.as_deref() == Some(&columns[..]));
+
+        let divan = divan.show_mad(false).show_cold(true);
+        let columns = [TreeColumn::Median, TreeColumn::Iqr, TreeColumn::Cold, TreeColumn::Samples];
+        assert!(divan.columns.as_deref() == Some(&columns[..]));
+    }
+
+    #[test]
+    #[should_panic = "invalid value 'size' for `--sort` (valid: kind, name, location)"]
+    fn cli_value_builders_invalid() {
+        _ = Divan::new().sort_by("size", false);
+    }
+
+    /// Tests that parents hidden by `--strip-prefix` are still part of
+    /// benchmark paths.
+    #[test]
+    fn run_tree_strip_prefix() {
+        use std::sync::OnceLock;
+
+        use crate::entry::{BenchEntry, EntryLocation, EntryMeta};
+
+        // Exceeding `max_allocs` records the full path of each benchmark.
+        let bench = |module_path: &'static str| -> AnyBenchEntry<'static> {
+            AnyBenchEntry::Bench(Box::leak(Box::new(BenchEntry {
+                meta: EntryMeta {
+                    display_name: "alloc",
+                    raw_name: "alloc",
+                    module_path,
+                    location: EntryLocation::default(),
+                    get_bench_options: Some(|| BenchOptions {
+                        max_allocs: Some(0),
+                        ..BenchOptions::default()
+                    }),
+                    cached_bench_options: OnceLock::new(),
+                },
+                bench: BenchEntryRunner::Plain(|bencher| bencher.bench(|| vec![1u8])),
+            })))
+        };
+
+        let tree = EntryTree::from_benches([bench("crate::a::b"), bench("crate::a::c")]);
+        let strip_prefix = EntryTree::common_prefix(&tree);
+        assert_eq!(strip_prefix, ["crate", "a"]);
+
+        let shared_context = crate::bench::tests::shared_context(Action::Test);
+
+        let tree_painter = RefCell::new(TreePainter::new(
+            EntryTree::max_stripped_name_span(&tree, &strip_prefix),
+            Vec::new(),
+            [0; TreeColumn::COUNT],
+            FineDuration::default(),
+            None,
+        ));
+
+        Divan::new().run_tree(
+            Action::Test,
+            &tree,
+            &shared_context,
+            None,
+            &strip_prefix,
+            &tree_painter,
+            None,
+            None,
+        );
+
+        let tree_painter = tree_painter.into_inner();
+        assert!(tree_painter.current_path().is_empty());
+
+        let paths: Vec<&str> =
+            tree_painter.max_allocs_exceeded().iter().map(|(path, ..)| path.as_str()).collect();
+        assert_eq!(paths, ["crate::a::b::alloc", "crate::a::c::alloc"]);
+    }
+
+    #[test]
+    #[should_panic = "peak bandwidth must be greater than 0"]
+    fn peak_bandwidth_zero() {
+        _ = Divan::new().peak_bandwidth(0);
+    }
+
+    /// Tests that ignored benchmarks give their share of `--deadline` to the
+    /// rest.
+    #[test]
+    fn deadline_skip_runs() {
+        let deadline = Duration::from_secs(1000);
+        let budget = DeadlineBudget {
+            end: Instant::now() + deadline,
+            remaining_runs: Cell::new(4),
+            reduced_count: Cell::new(0),
+        };
+
+        // An ignored benchmark with 2 arguments.
+        budget.skip_runs(2);
+
+        // The remaining 2 benchmarks split the time, and the last one gets
+        // whatever is left.
+        let share = budget.next_run();
+        assert!(share <= deadline / 2 && share > deadline / 3, "{share:?}");
+
+        let share = budget.next_run();
+        assert!(share <= deadline && share > deadline / 2, "{share:?}");
+
+        // Skipping more runs than remain does not underflow.
+        budget.skip_runs(10);
+        assert_eq!(budget.remaining_runs.get(), 0);
+    }
+}
@@ -1,4 +1,8 @@
-use std::{cmp::Ordering, ptr::NonNull};
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, HashMap},
+    ptr::NonNull,
+};
 
 use crate::{
     bench::{BenchOptions, DEFAULT_SAMPLE_COUNT},
@@ -6,6 +10,7 @@ use crate::{
     counter::KnownCounterKind,
     entry::{AnyBenchEntry, EntryLocation, EntryMeta, GenericBenchEntry, GroupEntry},
     tree_painter::TreeColumn,
+    util::rand::Rng,
 };
 
 /// `BenchEntry` tree organized by path components.
@@ -82,8 +87,49 @@ impl<'a> EntryTree<'a> {
             .unwrap_or_default()
     }
 
+    /// Returns the maximum span for a name in `tree` after hiding parents
+    /// stripped by `--strip-prefix`.
+    pub fn max_stripped_name_span(tree: &[Self], prefix: &[String]) -> usize {
+        tree.iter()
+            .map(|node| {
+                if node.is_stripped(prefix) {
+                    Self::max_stripped_name_span(node.children(), &prefix[1..])
+                } else {
+                    Self::max_name_span(std::slice::from_ref(node), 0)
+                }
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the names of the parents leading to the only top-level
+    /// benchmarks, for `--strip-common-prefix`.
+    pub fn common_prefix(mut tree: &[Self]) -> Vec<String> {
+        let mut prefix = Vec::new();
+
+        while let [node] = tree {
+            prefix.push(node.display_name().to_owned());
+
+            if !node.is_stripped(&prefix[prefix.len() - 1..]) {
+                prefix.pop();
+                break;
+            }
+
+            tree = node.children();
+        }
+
+        prefix
+    }
+
     /// Returns the likely span for a given column.
-    pub fn common_column_width(tree: &[Self], column: TreeColumn) -> usize {
+    ///
+    /// `run_options` are the options set at runtime, which override those of
+    /// entries.
+    pub fn common_column_width(
+        tree: &[Self],
+        column: TreeColumn,
+        run_options: &BenchOptions,
+    ) -> usize {
         // Time and throughput info.
         if column.is_time_stat() {
             return KnownCounterKind::MAX_COMMON_COLUMN_WIDTH;
@@ -91,8 +137,13 @@ impl<'a> EntryTree<'a> {
 
         tree.iter()
             .map(|tree| {
-                let Some(options) = tree.bench_options() else {
-                    return 0;
+                let overwritten_options;
+                let options = match tree.bench_options() {
+                    Some(entry_options) => {
+                        overwritten_options = run_options.overwrite(entry_options);
+                        &overwritten_options
+                    }
+                    None => run_options,
                 };
 
                 let width = match column {
@@ -101,12 +152,22 @@ impl<'a> EntryTree<'a> {
                         1 + sample_count.checked_ilog10().unwrap_or_default() as usize
                     }
 
-                    // Iters is the last column, so it does not need pad width.
+                    // Iters can only be known ahead of time if sample size
+                    // is not tuned.
+                    TreeColumn::Iters => match options.sample_size {
+                        Some(sample_size) => {
+                            let sample_count = options.sample_count.unwrap_or(DEFAULT_SAMPLE_COUNT);
+                            let iters = u64::from(sample_count) * u64::from(sample_size);
+                            1 + iters.checked_ilog10().unwrap_or_default() as usize
+                        }
+                        None => 0,
+                    },
+
                     // All other columns are time stats handled previously.
                     _ => 0,
                 };
 
-                width.max(Self::common_column_width(tree.children(), column))
+                width.max(Self::common_column_width(tree.children(), column, run_options))
             })
             .max()
             .unwrap_or_default()
@@ -149,6 +210,71 @@ impl<'a> EntryTree<'a> {
         }
     }
 
+    /// Returns the paths and locations of leaves that have the same path as
+    /// an earlier leaf.
+    pub fn duplicate_leaves(tree: &[Self]) -> Vec<(String, [&'a EntryLocation; 2])> {
+        fn find<'a>(
+            tree: &[EntryTree<'a>],
+            parent_path: &str,
+            result: &mut Vec<(String, [&'a EntryLocation; 2])>,
+        ) {
+            let mut leaf_locations = HashMap::<&str, &'a EntryLocation>::new();
+
+            for subtree in tree {
+                let path = if parent_path.is_empty() {
+                    subtree.display_name().to_owned()
+                } else {
+                    format!("{parent_path}::{}", subtree.display_name())
+                };
+
+                match subtree {
+                    EntryTree::Parent { children, .. } => find(children, &path, result),
+
+                    EntryTree::Leaf { entry, .. } => {
+                        let location = &entry.meta().location;
+
+                        match leaf_locations.entry(subtree.display_name()) {
+                            Entry::Vacant(slot) => _ = slot.insert(location),
+                            Entry::Occupied(slot) => result.push((path, [slot.get(), location])),
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        find(tree, "", &mut result);
+        result
+    }
+
+    /// Returns the full path of each benchmark in order, including generic
+    /// types, constants, and `args` values.
+    pub fn leaf_paths(tree: &[Self]) -> Vec<String> {
+        fn collect(tree: &[EntryTree], parent_path: &str, result: &mut Vec<String>) {
+            for subtree in tree {
+                let path = if parent_path.is_empty() {
+                    subtree.display_name().to_owned()
+                } else {
+                    format!("{parent_path}::{}", subtree.display_name())
+                };
+
+                match subtree {
+                    EntryTree::Parent { children, .. } => collect(children, &path, result),
+
+                    EntryTree::Leaf { args: Some(args), .. } => {
+                        result.extend(args.iter().map(|arg| format!("{path}::{arg}")));
+                    }
+
+                    EntryTree::Leaf { args: None, .. } => result.push(path),
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        collect(tree, "", &mut result);
+        result
+    }
+
     /// Removes entries from the tree whose paths do not match the filter.
     pub fn retain(tree: &mut Vec<Self>, mut filter: impl FnMut(&str) -> bool) {
         fn retain(
@@ -187,6 +313,61 @@ impl<'a> EntryTree<'a> {
         retain(tree, "", &mut filter);
     }
 
+    /// Removes benchmarks from the tree whose definition location does not
+    /// match the filter.
+    pub fn retain_by_location(
+        tree: &mut Vec<Self>,
+        filter: &mut impl FnMut(&EntryLocation) -> bool,
+    ) {
+        tree.retain_mut(|subtree| match subtree {
+            EntryTree::Parent { children, .. } => {
+                Self::retain_by_location(children, filter);
+
+                // If no children exist, filter out this parent.
+                !children.is_empty()
+            }
+
+            EntryTree::Leaf { entry, .. } => filter(&entry.meta().location),
+        });
+    }
+
+    /// Removes entries from the tree whose options do not match the filter.
+    ///
+    /// Options are resolved by overwriting parent options with child options,
+    /// the same as when running the tree.
+    pub fn retain_by_options(tree: &mut Vec<Self>, mut filter: impl FnMut(&BenchOptions) -> bool) {
+        fn retain(
+            tree: &mut Vec<EntryTree>,
+            parent_options: Option<&BenchOptions>,
+            filter: &mut impl FnMut(&BenchOptions) -> bool,
+        ) {
+            tree.retain_mut(|subtree| {
+                let options: BenchOptions;
+                let options: Option<&BenchOptions> = match (parent_options, subtree.bench_options())
+                {
+                    (None, None) => None,
+                    (Some(options), None) | (None, Some(options)) => Some(options),
+                    (Some(parent_options), Some(child_options)) => {
+                        options = child_options.overwrite(parent_options);
+                        Some(&options)
+                    }
+                };
+
+                match subtree {
+                    EntryTree::Parent { children, .. } => {
+                        retain(children, options, filter);
+
+                        // If no children exist, filter out this parent.
+                        !children.is_empty()
+                    }
+
+                    EntryTree::Leaf { .. } => filter(options.unwrap_or(&BenchOptions::default())),
+                }
+            });
+        }
+        retain(tree, None, &mut filter);
+    }
+
     /// Sorts the tree by the given ordering.
     pub fn sort_by_attr(tree: &mut [Self], attr: SortingAttr, reverse: bool) {
         let apply_reverse =
@@ -211,6 +392,21 @@ impl<'a> EntryTree<'a> {
         });
     }
 
+    /// Randomly reorders siblings and arguments at every level of the tree.
+    pub fn shuffle(tree: &mut [Self], rng: &mut Rng) {
+        rng.shuffle(tree);
+
+        tree.iter_mut().for_each(|tree| match tree {
+            EntryTree::Leaf { args, .. } => {
+                if let Some(args) = args {
+                    rng.shuffle(args);
+                }
+            }
+
+            EntryTree::Parent { children, .. } => Self::shuffle(children, rng),
+        });
+    }
+
     fn cmp_by_attr(&self, other: &Self, attr: SortingAttr) -> Ordering {
         // We take advantage of the fact that entries have stable addresses,
         // unlike `EntryTree`.
@@ -327,7 +523,7 @@ impl<'a> EntryTree<'a> {
         }
     }
 
-    pub fn bench_options(&self) -> Option<&'a BenchOptions> {
+    pub fn bench_options(&self) -> Option<&'a BenchOptions<'a>> {
         self.meta()?.bench_options()
     }
 
@@ -388,6 +584,18 @@ impl<'a> EntryTree<'a> {
         }
     }
 
+    /// Returns `true` if this is a parent named by the first component of
+    /// `prefix` that can be hidden without showing benchmarks at the top level.
+    pub fn is_stripped(&self, prefix: &[String]) -> bool {
+        match self {
+            Self::Parent { children, .. } => {
+                prefix.first().is_some_and(|name| name == self.display_name())
+                    && children.iter().all(|child| matches!(child, Self::Parent { .. }))
+            }
+            Self::Leaf { .. } => false,
+        }
+    }
+
     fn children(&self) -> &[Self] {
         match self {
             Self::Leaf { .. } => &[],
@@ -402,3 +610,125 @@ impl<'a> EntryTree<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+
+    use super::*;
+    use crate::entry::{BenchEntry, BenchEntryRunner};
+
+    /// Returns a leaked benchmark entry at `path`, defined on `line`.
+    fn bench(path: &'static str, line: u32) -> AnyBenchEntry<'static> {
+        let (module_path, name) = path.rsplit_once("::").unwrap();
+
+        AnyBenchEntry::Bench(Box::leak(Box::new(BenchEntry {
+            meta: EntryMeta {
+                display_name: name,
+                raw_name: name,
+                module_path,
+                location: EntryLocation { file: "bench.rs", line, col: 1 },
+                get_bench_options: None,
+                cached_bench_options: OnceLock::new(),
+            },
+            bench: BenchEntryRunner::Plain(|_| {}),
+        })))
+    }
+
+    #[test]
+    fn duplicate_leaves() {
+        let tree = EntryTree::from_benches([
+            bench("crate::math::add", 1),
+            bench("crate::math::sub", 2),
+            bench("crate::math::add", 3),
+            bench("crate::other::add", 4),
+            bench("crate::math::add", 5),
+        ]);
+
+        let duplicates: Vec<(String, u32, u32)> = EntryTree::duplicate_leaves(&tree)
+            .into_iter()
+            .map(|(path, [first, second])| (path, first.line, second.line))
+            .collect();
+
+        // Each later leaf is reported against the first, and same names in
+        // different modules are not duplicates.
+        assert_eq!(
+            duplicates,
+            [("crate::math::add".to_owned(), 1, 3), ("crate::math::add".to_owned(), 1, 5)],
+        );
+
+        assert!(EntryTree::duplicate_leaves(&[]).is_empty());
+    }
+
+    #[test]
+    fn is_stripped() {
+        let tree = EntryTree::from_benches([
+            bench("crate::group::sub::a", 1),
+            bench("crate::group::sub::b", 2),
+            bench("crate::leaves::c", 3),
+        ]);
+
+        let [root] = &tree[..] else { panic!() };
+        let prefix =
+            |names: &[&str]| -> Vec<String> { names.iter().map(|&name| name.to_owned()).collect() };
+
+        assert!(root.is_stripped(&prefix(&["crate"])));
+        assert!(root.is_stripped(&prefix(&["crate", "other"])));
+
+        // Names that don't match or are missing are not stripped.
+        assert!(!root.is_stripped(&prefix(&["other"])));
+        assert!(!root.is_stripped(&[]));
+
+        let [group, leaves] = root.children() else { panic!() };
+        assert!(group.is_stripped(&prefix(&["group"])));
+
+        // Groups that directly contain benchmarks are not stripped, since the
+        // benchmarks would otherwise appear at the top level.
+        assert!(!leaves.is_stripped(&prefix(&["leaves"])));
+        assert!(!leaves.children()[0].is_stripped(&prefix(&["c"])));
+    }
+
+    #[test]
+    fn common_prefix() {
+        assert!(EntryTree::common_prefix(&[]).is_empty());
+
+        // Stops before the group that directly contains benchmarks.
+        let tree =
+            EntryTree::from_benches([bench("crate::a::b::c::x", 1), bench("crate::a::b::c::y", 2)]);
+        assert_eq!(EntryTree::common_prefix(&tree), ["crate", "a", "b"]);
+
+        // Stops at the first group with multiple children.
+        let tree =
+            EntryTree::from_benches([bench("crate::a::b::x", 1), bench("crate::a::c::y", 2)]);
+        assert_eq!(EntryTree::common_prefix(&tree), ["crate", "a"]);
+
+        // Nothing is common across multiple top-level groups.
+        let tree = EntryTree::from_benches([bench("a::b::x", 1), bench("c::d::y", 2)]);
+        assert!(EntryTree::common_prefix(&tree).is_empty());
+    }
+
+    #[test]
+    fn max_stripped_name_span() {
+        assert_eq!(EntryTree::max_stripped_name_span(&[], &[]), 0);
+
+        let tree = EntryTree::from_benches([bench("crate::group::sub::bench_name", 1)]);
+        let prefix =
+            |names: &[&str]| -> Vec<String> { names.iter().map(|&name| name.to_owned()).collect() };
+
+        // "         bench_name" at depth 3.
+        let full_span = EntryTree::max_name_span(&tree, 0);
+        assert_eq!(full_span, 19);
+
+        assert_eq!(EntryTree::max_stripped_name_span(&tree, &[]), full_span);
+        assert_eq!(EntryTree::max_stripped_name_span(&tree, &prefix(&["other"])), full_span);
+
+        // "   bench_name" at depth 1 after hiding "crate" and "group".
+        assert_eq!(EntryTree::max_stripped_name_span(&tree, &prefix(&["crate", "group"])), 13);
+
+        // "sub" directly contains a benchmark, so it stays.
+        assert_eq!(
+            EntryTree::max_stripped_name_span(&tree, &prefix(&["crate", "group", "sub"])),
+            13,
+        );
+    }
+}
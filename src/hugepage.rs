@@ -0,0 +1,222 @@
+//! Hugepage-backed buffers for benchmarking over large inputs.
+//!
+//! On Linux, allocation first tries explicit hugepages via `MAP_HUGETLB`, which
+//! requires pages reserved through `/proc/sys/vm/nr_hugepages`. Otherwise it
+//! maps normal pages and requests transparent hugepages via
+//! `madvise(MADV_HUGEPAGE)`, which requires
+//! `/sys/kernel/mm/transparent_hugepage/enabled` to be `always` or `madvise`.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// Size of hugepages on common Linux targets, to which mapped lengths are
+/// rounded up.
+#[cfg(all(target_os = "linux", not(miri)))]
+const HUGEPAGE_SIZE: usize = 2 * 1024 * 1024;
+
+/// Allocates a zeroed buffer of `len` bytes backed by hugepages, for use as
+/// benchmark input.
+///
+/// Benchmarks over multi-gigabyte buffers are often dominated by TLB misses
+/// with normal 4 KiB pages, which obscures measurements of memory bandwidth.
+/// Hugepages cover the same memory with far fewer TLB entries.
+///
+/// This requires the `hugepages` crate feature and is currently only supported
+/// on Linux. It tries, in order:
+///
+/// 1. Explicit hugepages via `mmap(MAP_HUGETLB)`, which requires pages to be
+///    reserved, such as with `sysctl vm.nr_hugepages=1024`.
+///
+/// 2. Transparent hugepages via `madvise(MADV_HUGEPAGE)`, which requires
+///    `/sys/kernel/mm/transparent_hugepage/enabled` to be `always` or
+///    `madvise`. The kernel may still back some of the buffer with normal
+///    pages.
+///
+/// If neither is available, or on other platforms, this warns and falls back
+/// to a normal allocation. [`HugePageBuf::is_huge`] reports which was used.
+///
+/// # Examples
+///
+/// ```
+/// #[divan::bench]
+/// fn sum(bencher: divan::Bencher) {
+///     let buf = divan::alloc_hugepage(1 << 30);
+///
+///     bencher.bench(|| {
+///         divan::black_box(&buf[..]).iter().map(|&b| b as u64).sum::<u64>()
+///     });
+/// }
+/// ```
+pub fn alloc_hugepage(len: usize) -> HugePageBuf {
+    #[cfg(all(target_os = "linux", not(miri)))]
+    if let Some(buf) = linux::alloc(len) {
+        return buf;
+    }
+
+    eprintln!("warning: Hugepages are unavailable, using normal pages for {len}-byte buffer");
+
+    HugePageBuf { backing: Backing::Heap(vec![0; len].into_boxed_slice()) }
+}
+
+/// A zeroed byte buffer allocated by [`alloc_hugepage`], which is unmapped
+/// when dropped.
+pub struct HugePageBuf {
+    backing: Backing,
+}
+
+enum Backing {
+    #[cfg(all(target_os = "linux", not(miri)))]
+    Mapped(linux::Mapping),
+    Heap(Box<[u8]>),
+}
+
+impl HugePageBuf {
+    /// Returns `true` if hugepages were requested successfully, or `false` if
+    /// this fell back to normal pages.
+    #[inline]
+    pub fn is_huge(&self) -> bool {
+        match &self.backing {
+            #[cfg(all(target_os = "linux", not(miri)))]
+            Backing::Mapped(_) => true,
+            Backing::Heap(_) => false,
+        }
+    }
+}
+
+impl Deref for HugePageBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        match &self.backing {
+            #[cfg(all(target_os = "linux", not(miri)))]
+            Backing::Mapped(mapping) => mapping.as_slice(),
+            Backing::Heap(heap) => heap,
+        }
+    }
+}
+
+impl DerefMut for HugePageBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match &mut self.backing {
+            #[cfg(all(target_os = "linux", not(miri)))]
+            Backing::Mapped(mapping) => mapping.as_mut_slice(),
+            Backing::Heap(heap) => heap,
+        }
+    }
+}
+
+impl fmt::Debug for HugePageBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HugePageBuf")
+            .field("len", &self.len())
+            .field("huge", &self.is_huge())
+            .finish()
+    }
+}
+
+#[cfg(all(target_os = "linux", not(miri)))]
+mod linux {
+    use std::{ptr, slice};
+
+    use super::{Backing, HugePageBuf, HUGEPAGE_SIZE};
+
+    /// An anonymous memory mapping.
+    pub(super) struct Mapping {
+        ptr: *mut u8,
+        len: usize,
+        map_len: usize,
+    }
+
+    // SAFETY: The mapping is uniquely owned like `Box<[u8]>`.
+    unsafe impl Send for Mapping {}
+    unsafe impl Sync for Mapping {}
+
+    impl Mapping {
+        /// Maps `len` bytes rounded up to the hugepage size.
+        fn new(len: usize, flags: libc::c_int) -> Option<Self> {
+            let map_len = len.max(1).checked_next_multiple_of(HUGEPAGE_SIZE)?;
+
+            // SAFETY: Anonymous mappings do not alias any existing memory.
+            let ptr = unsafe {
+                libc::mmap(
+                    ptr::null_mut(),
+                    map_len,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | flags,
+                    -1,
+                    0,
+                )
+            };
+
+            if ptr == libc::MAP_FAILED {
+                None
+            } else {
+                Some(Self { ptr: ptr.cast(), len, map_len })
+            }
+        }
+
+        #[inline]
+        pub fn as_slice(&self) -> &[u8] {
+            // SAFETY: Anonymous mappings are zero-initialized.
+            unsafe { slice::from_raw_parts(self.ptr, self.len) }
+        }
+
+        #[inline]
+        pub fn as_mut_slice(&mut self) -> &mut [u8] {
+            // SAFETY: Anonymous mappings are zero-initialized.
+            unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+
+    impl Drop for Mapping {
+        fn drop(&mut self) {
+            // SAFETY: `ptr` and `map_len` were returned from `mmap`.
+            unsafe { libc::munmap(self.ptr.cast(), self.map_len) };
+        }
+    }
+
+    pub(super) fn alloc(len: usize) -> Option<HugePageBuf> {
+        let mapping = match Mapping::new(len, libc::MAP_HUGETLB) {
+            Some(mapping) => mapping,
+            None => {
+                let mapping = Mapping::new(len, 0)?;
+
+                // SAFETY: The range is exactly the mapping. Failure leaves
+                // normal pages, such as if transparent hugepages are disabled,
+                // in which case the mapping is dropped for the fallback.
+                let result = unsafe {
+                    libc::madvise(mapping.ptr.cast(), mapping.map_len, libc::MADV_HUGEPAGE)
+                };
+
+                if result != 0 {
+                    return None;
+                }
+
+                mapping
+            }
+        };
+
+        Some(HugePageBuf { backing: Backing::Mapped(mapping) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zeroed_and_writable() {
+        for len in [0, 1, 4096, 3 * 1024 * 1024] {
+            let mut buf = alloc_hugepage(len);
+            assert_eq!(buf.len(), len);
+            assert!(buf.iter().all(|&b| b == 0));
+
+            buf.fill(0xAB);
+            assert!(buf.iter().all(|&b| b == 0xAB));
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Graceful handling of Ctrl-C (`SIGINT`).
+//!
+//! With the `ctrl_c` crate feature on Unix, the first interrupt stops sampling
+//! the current benchmark and prevents any remaining benchmarks from running.
+//! The second interrupt exits the process immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Returns `true` if the run was interrupted by the user.
+#[inline]
+pub(crate) fn is_interrupted() -> bool {
+    INTERRUPTED.load(Relaxed)
+}
+
+/// Restores the previous signal handler when dropped.
+pub(crate) struct HandlerGuard {
+    #[cfg(all(feature = "ctrl_c", unix, not(miri)))]
+    prev_action: libc::sigaction,
+}
+
+impl HandlerGuard {
+    /// Installs the `SIGINT` handler for the duration of the guard.
+    ///
+    /// The handler is not installed if one was already set, such as by the
+    /// benchmarked program itself.
+    pub fn install() -> Option<Self> {
+        cfg_if::cfg_if! {
+            if #[cfg(all(feature = "ctrl_c", unix, not(miri)))] {
+                use std::{mem, ptr};
+
+                extern "C" fn handle_sigint(_: libc::c_int) {
+                    if INTERRUPTED.swap(true, Relaxed) {
+                        // SAFETY: `_exit` is async-signal-safe.
+                        unsafe { libc::_exit(130) }
+                    }
+                }
+
+                // SAFETY: `sigaction` is zero-initializable and the pointers
+                // passed to libc are valid.
+                unsafe {
+                    let mut prev_action: libc::sigaction = mem::zeroed();
+                    if libc::sigaction(libc::SIGINT, ptr::null(), &mut prev_action) != 0
+                        || prev_action.sa_sigaction != libc::SIG_DFL
+                    {
+                        return None;
+                    }
+
+                    let mut action: libc::sigaction = mem::zeroed();
+                    action.sa_sigaction = handle_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                    libc::sigemptyset(&mut action.sa_mask);
+
+                    if libc::sigaction(libc::SIGINT, &action, &mut prev_action) != 0 {
+                        return None;
+                    }
+
+                    Some(Self { prev_action })
+                }
+            } else {
+                None
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "ctrl_c", unix, not(miri)))]
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        // SAFETY: `prev_action` was retrieved from `sigaction`.
+        unsafe { libc::sigaction(libc::SIGINT, &self.prev_action, std::ptr::null_mut()) };
+    }
+}
@@ -15,9 +15,16 @@ mod bench;
 mod cli;
 mod compile_fail;
 mod config;
+mod cpufreq;
 mod divan;
 mod entry;
+#[cfg(feature = "hugepages")]
+mod hugepage;
+mod interrupt;
+mod priority;
 mod stats;
+mod structure;
+mod summary;
 mod time;
 mod tree_painter;
 mod util;
@@ -116,7 +123,10 @@ pub mod counter;
 pub use std::hint::black_box;
 
 #[doc(inline)]
-pub use crate::{alloc::AllocProfiler, bench::Bencher, divan::Divan};
+pub use crate::{alloc::AllocProfiler, bench::Bencher, divan::Divan, stats::SampleStats};
+
+#[cfg(feature = "hugepages")]
+pub use crate::hugepage::{alloc_hugepage, HugePageBuf};
 
 /// Runs all registered benchmarks.
 ///
@@ -167,6 +177,234 @@ pub fn black_box_drop<T>(dummy: T) {
     _ = black_box(dummy);
 }
 
+/// [`black_box`] for a shared reference, without moving the referenced value.
+///
+/// This hides from the compiler what `value` points to, which prevents
+/// benchmarked code from being optimized based on known setup data. The value
+/// itself can still be used afterward. This is equivalent to
+/// `black_box(&value)`, but with the reference type inferred from the argument.
+///
+/// Use [`black_box`] by value when the benchmark should consume its input or
+/// when the input is cheap to copy, such as integers.
+///
+/// # Examples
+///
+/// ```
+/// #[divan::bench]
+/// fn sum(bencher: divan::Bencher) {
+///     let values: Vec<u64> = (0..1000).collect();
+///
+///     bencher.bench(|| divan::black_box_ref(&values).iter().sum::<u64>());
+///
+///     // `values` is still owned here.
+///     drop(values);
+/// }
+/// ```
+#[inline]
+pub fn black_box_ref<T: ?Sized>(value: &T) -> &T {
+    black_box(value)
+}
+
+/// [`black_box`] for a mutable reference, without moving the referenced value.
+///
+/// This is like [`black_box_ref`], but also makes the compiler assume that the
+/// value may be modified through the returned reference.
+///
+/// # Examples
+///
+/// ```
+/// #[divan::bench]
+/// fn clear(bencher: divan::Bencher) {
+///     let mut buf = String::with_capacity(64);
+///
+///     bencher.bench_local(|| divan::black_box_mut(&mut buf).clear());
+/// }
+/// ```
+#[inline]
+pub fn black_box_mut<T: ?Sized>(value: &mut T) -> &mut T {
+    black_box(value)
+}
+
+/// Writes to every page of `buf` to make the OS back it with physical memory.
+///
+/// Operating systems like Linux lazily allocate large buffers (especially with
+/// memory overcommit), deferring the cost to a page fault on first access. If
+/// that happens in the measured region, page faults may dominate timings.
+/// Pre-touching pages when generating inputs moves this cost out of the
+/// measurement. The contents of `buf` are left unchanged.
+///
+/// Unlike the untimed iterations Divan runs when tuning [`sample_size`], this
+/// applies to buffers that are freshly allocated for each iteration, such as
+/// via [`Bencher::with_inputs`].
+///
+/// This assumes pages are at least 4 KiB, which is the smallest page size on
+/// common platforms.
+///
+/// [`sample_size`]: macro@bench#sample_size
+///
+/// # Examples
+///
+/// ```
+/// #[divan::bench]
+/// fn fill(bencher: divan::Bencher) {
+///     bencher
+///         .with_inputs(|| {
+///             let mut buf = vec![0u8; 1 << 20];
+///             divan::pre_touch(&mut buf);
+///             buf
+///         })
+///         .bench_refs(|buf| buf.fill(1));
+/// }
+/// ```
+pub fn pre_touch(buf: &mut [u8]) {
+    const PAGE_SIZE: usize = 4096;
+
+    for byte in buf.iter_mut().step_by(PAGE_SIZE) {
+        let byte: *mut u8 = byte;
+
+        // SAFETY: The pointer is derived from a mutable reference. Volatile
+        // access prevents the redundant write from being optimized out.
+        unsafe { byte.write_volatile(byte.read_volatile()) };
+    }
+}
+
+/// Returns all bytes from standard input, for use as benchmark input supplied
+/// by an external driver, such as a fuzzer or corpus runner.
+///
+/// Standard input is read to EOF on the first call, and the same bytes are
+/// returned by every later call, including from other benchmarks and threads.
+/// Divan itself never reads standard input, so it is fully available to this.
+///
+/// Reading happens outside of any timed region as long as this is called
+/// outside of the benchmarked function, such as before [`Bencher::bench`].
+/// Otherwise, only the first iteration would include the read.
+///
+/// If reading fails, Divan prints an error and exits.
+///
+/// # Examples
+///
+/// ```
+/// #[divan::bench]
+/// fn parse(bencher: divan::Bencher) {
+///     let input = divan::read_stdin_bytes();
+///
+///     bencher.bench(|| {
+///         std::str::from_utf8(divan::black_box(input)).is_ok()
+///     });
+/// }
+/// ```
+///
+/// Each invocation then measures one input:
+///
+/// ```sh
+/// cargo bench --bench parse < corpus/input-1.json
+/// ```
+pub fn read_stdin_bytes() -> &'static [u8] {
+    use std::{io::Read, sync::OnceLock};
+
+    static STDIN_BYTES: OnceLock<Vec<u8>> = OnceLock::new();
+
+    STDIN_BYTES.get_or_init(|| {
+        let mut bytes = Vec::new();
+
+        if let Err(error) = std::io::stdin().lock().read_to_end(&mut bytes) {
+            eprintln!("error: Failed to read standard input: {error}");
+            std::process::exit(1);
+        }
+
+        bytes
+    })
+}
+
+/// Measures the median time taken by `benched`, outside of the benchmark
+/// harness.
+///
+/// This is meant for coarse performance guards in regular `#[test]` functions.
+/// It takes few samples under whatever load the test runner is under, so
+/// results are noisy and thresholds should leave plenty of headroom. Use
+/// [`#[divan::bench]`](macro@bench) for precise measurements.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let median = divan::measure(|| divan::black_box("1234").parse::<u32>());
+/// assert!(median < Duration::from_millis(5));
+/// ```
+pub fn measure<O, B>(benched: B) -> std::time::Duration
+where
+    B: Fn() -> O + Sync,
+{
+    let options = bench::BenchOptions {
+        sample_count: Some(20),
+        max_time: Some(std::time::Duration::from_secs(1)),
+        ..bench::BenchOptions::default()
+    };
+
+    measure_standalone(&options, |bencher| bencher.bench(benched)).time.median.as_duration_lossy()
+}
+
+/// Benchmarks a block in place, outside of the benchmark harness, and prints
+/// and returns its [`SampleStats`].
+///
+/// This is meant for quick one-off measurements in `main` or tests without
+/// declaring a [`#[divan::bench]`](macro@bench) function. The block runs
+/// immediately at the call site using the default sample count and automatic
+/// sample size, and its output is [`black_box`]ed like a benchmark's return
+/// value.
+///
+/// Since nothing is registered, the block does not participate in
+/// [`divan::main`](crate::main): it is not affected by filters or CLI
+/// arguments, and is not included in the normal run's output. Results are
+/// printed on a single line to stdout and can be checked via the returned
+/// stats.
+///
+/// # Examples
+///
+/// ```
+/// let input = vec![3, 1, 2];
+///
+/// let stats = divan::bench_block!("sort", {
+///     let mut input = divan::black_box(input.clone());
+///     input.sort();
+///     input
+/// });
+///
+/// assert!(stats.fastest <= stats.median);
+/// ```
+#[macro_export]
+macro_rules! bench_block {
+    ($name:expr, $block:block $(,)?) => {
+        $crate::__private::bench_block($name, || $block)
+    };
+}
+
+/// Runs a benchmark outside of the harness with the OS timer.
+pub(crate) fn measure_standalone(
+    options: &bench::BenchOptions,
+    bench: impl FnOnce(Bencher),
+) -> stats::Stats {
+    use crate::{bench::BenchContext, config::Action, divan::SharedContext, time::Timer};
+
+    let timer = Timer::Os;
+
+    let shared_context = SharedContext {
+        action: Action::Bench,
+        timer,
+        bench_overhead: timer.measure_sample_loop_overhead(),
+        measure_setup: false,
+        deterministic: false,
+        measure_cold: false,
+    };
+
+    let mut bench_context =
+        BenchContext::new(&shared_context, options, std::num::NonZeroUsize::MIN);
+    bench(Bencher::new(&mut bench_context));
+
+    bench_context.compute_stats()
+}
+
 /// Registers a benchmarking function.
 ///
 /// # Examples
@@ -248,6 +486,8 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// The [`black_box_return`] option opts out of this for advanced use cases.
+///
 /// If the returned value *does not* need to be dropped, there is no memory
 /// cost. Because of this, the following example benchmarks are equivalent:
 ///
@@ -267,15 +507,95 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// # Inlining
+///
+/// Benchmarked functions are not called through function pointers. The sample
+/// loop is generated for each benchmark's function type, so the compiler may
+/// inline the function into the loop like any other direct call. This applies
+/// to functions marked `#[inline(always)]` and to closures passed to
+/// [`Bencher::bench`].
+///
+/// The per-iteration cost of the sample loop itself is measured before running
+/// and subtracted from samples.
+///
+/// To measure the cost of a call that cannot be inlined, mark the function
+/// `#[inline(never)]`:
+///
+/// ```
+/// #[divan::bench]
+/// #[inline(never)]
+/// fn add() -> i32 {
+///     divan::black_box(1) + divan::black_box(2)
+/// }
+/// ```
+///
+/// # Static vs dynamic dispatch
+///
+/// Since the sample loop makes a direct call to the benchmarked function, the
+/// harness adds the same overhead whether the function uses generics or
+/// `dyn Trait`. Any dynamic call measured is one made by the benchmarked code.
+///
+/// However, the compiler can remove the difference being measured:
+///
+/// - If the concrete type behind a `dyn Trait` is visible, the call may be
+///   devirtualized into a direct call. Passing the trait object through
+///   [`black_box`] hides its type.
+///
+/// - Marking the statically-dispatched function `#[inline(never)]` prevents
+///   the inlining that often makes static dispatch faster, so only the vtable
+///   lookup would be compared.
+///
+/// Both can then be compared using the same input:
+///
+/// ```
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+///     shapes.iter().map(S::area).sum()
+/// }
+///
+/// fn total_area_dyn(shapes: &[&dyn Shape]) -> f64 {
+///     shapes.iter().map(|s| s.area()).sum()
+/// }
+///
+/// #[divan::bench]
+/// fn static_dispatch(bencher: divan::Bencher) {
+///     let shapes: Vec<Square> = (0..100).map(|i| Square(i as f64)).collect();
+///
+///     bencher.bench_local(|| total_area(divan::black_box(&shapes)));
+/// }
+///
+/// #[divan::bench]
+/// fn dynamic_dispatch(bencher: divan::Bencher) {
+///     let shapes: Vec<Square> = (0..100).map(|i| Square(i as f64)).collect();
+///     let shapes: Vec<&dyn Shape> = shapes.iter().map(|s| s as &dyn Shape).collect();
+///
+///     bencher.bench_local(|| total_area_dyn(divan::black_box(&shapes)));
+/// }
+/// ```
+///
 /// # Options
 ///
 /// - [`name`]
 /// - [`crate`]
 /// - [`args`]
+///     - [`black_box_args`]
+///     - [`args_as_items`]
 /// - [`consts`]
 /// - [`types`]
 /// - [`sample_count`]
 /// - [`sample_size`]
+/// - [`drop_first`]
 /// - [`threads`]
 /// - [`counters`]
 ///     - [`bytes_count`]
@@ -283,8 +603,13 @@ pub fn black_box_drop<T>(dummy: T) {
 ///     - [`items_count`]
 /// - [`min_time`]
 /// - [`max_time`]
+/// - [`min_samples`]
 /// - [`skip_ext_time`]
+/// - [`max_allocs`]
+/// - [`tags`]
 /// - [`ignore`]
+/// - [`should_panic`]
+/// - [`black_box_return`]
 ///
 /// ## `name`
 /// [`name`]: #name
@@ -431,6 +756,63 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// ### `black_box_args`
+/// [`black_box_args`]: #black_box_args
+///
+/// Since [`args`] values are often known at compile time, the compiler may
+/// optimize the benchmarked function for those specific values. The
+/// [`black_box_args`] option passes each argument through [`black_box`] to
+/// prevent this, without needing to remember to do so in every benchmark:
+///
+/// ```
+/// #[divan::bench(args = [1, 2, 4, 8], black_box_args)]
+/// fn fibonacci(n: u64) -> u64 {
+///     // Is not constant-folded for `n`.
+///     # 0
+/// }
+/// ```
+///
+/// If the function takes a [`Bencher`], the argument is only passed through
+/// [`black_box`] once when calling the function. Values used within
+/// [`Bencher::bench`] and similar closures must still be passed through
+/// [`black_box`] manually:
+///
+/// ```
+/// use divan::{black_box, Bencher};
+///
+/// #[divan::bench(args = [1, 2, 4, 8], black_box_args)]
+/// fn fibonacci(bencher: Bencher, n: u64) {
+///     bencher.bench(|| {
+///         // ...
+///         # black_box(n)
+///     });
+/// }
+/// ```
+///
+/// ### `args_as_items`
+/// [`args_as_items`]: #args_as_items
+///
+/// When each argument is the amount of work done by the benchmark, such as a
+/// collection length, the [`args_as_items`] option also uses it as the
+/// [`ItemsCount`](crate::counter::ItemsCount) for reporting throughput. This
+/// avoids setting the counter manually for each case:
+///
+/// ```
+/// #[divan::bench(args = [1000, 1_000_000], args_as_items)]
+/// fn sum(n: u64) -> u64 {
+///     // Reports throughput of `n` items.
+///     (0..n).sum()
+/// }
+/// ```
+///
+/// Similarly, `args_as_bytes` and `args_as_chars` use the argument as the
+/// [`BytesCount`](crate::counter::BytesCount) or
+/// [`CharsCount`](crate::counter::CharsCount). The argument must be an unsigned
+/// integer or a reference to one, and is counted before being passed through
+/// [`black_box_args`]. If the function takes a [`Bencher`], the counter is set
+/// on it before it is passed in, so it is overridden by calling
+/// [`Bencher::counter`] with the same counter type.
+///
 /// ## `consts`
 /// [`consts`]: #consts
 ///
@@ -557,6 +939,11 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// The runtime override can be limited to benchmarks at or under a path via
+/// `--sample-count <PATH>=<N>`, such as `--sample-count math::fibonacci=1000`.
+/// The most specific path takes precedence, followed by the unscoped
+/// `--sample-count` or `DIVAN_SAMPLE_COUNT`, followed by the attribute option.
+///
 /// If the [`threads`] option is enabled, sample count becomes a multiple of the
 /// number of threads. This is because each thread operates over the same sample
 /// size to ensure there are always N competing threads doing the same amount of
@@ -568,7 +955,8 @@ pub fn black_box_drop<T>(dummy: T) {
 /// The number iterations within each statistics sample can be set to a
 /// predetermined [`u32`] value via the [`sample_size`] option. This may be
 /// overridden at runtime using either the `DIVAN_SAMPLE_SIZE` environment
-/// variable or `--sample-size` CLI argument.
+/// variable or `--sample-size` CLI argument. Like [`sample_count`], the runtime
+/// override can be limited to a path via `--sample-size <PATH>=<N>`.
 ///
 /// ```
 /// #[divan::bench(sample_size = 1000)]
@@ -578,6 +966,56 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// Each sample times [`sample_size`] consecutive iterations as a single
+/// measurement, which is then divided by [`sample_size`] to get the time per
+/// iteration. This makes [`sample_size`] the number of inner iterations, whereas
+/// [`sample_count`] is the number of measurements used for statistics. Setting a
+/// large [`sample_size`] is useful for operations that are too fast to be
+/// measured individually by the timer.
+///
+/// Iterations within a sample run back-to-back with nothing else in between,
+/// so [`sample_size`] also acts as the batch size for reaching a steady cache
+/// state. For cache-sensitive code where a single iteration does not touch
+/// enough data to warm the cache, set [`sample_size`] to at least the number of
+/// iterations needed; the timer is then read once per batch rather than once
+/// per iteration. There is no separate batching option: Divan does not unroll
+/// or repeat the benchmarked function within an iteration.
+///
+/// If not set, [`sample_size`] is tuned automatically so that each sample takes
+/// much longer than the timer's precision. If set such that samples take less
+/// than 10x the timer's precision, a note is printed after running because
+/// timings may then be dominated by the overhead of reading the timer.
+///
+/// In the output, the `samples` column is the number of samples recorded and the
+/// `iters` column is the total number of iterations across all of them, i.e.
+/// `samples × sample_size`.
+///
+/// ## `drop_first`
+/// [`drop_first`]: #drop_first
+///
+/// The first samples can be excluded from statistics via the [`drop_first`]
+/// option. This may be overridden at runtime using either the
+/// `DIVAN_DROP_FIRST` environment variable or `--drop-first` CLI argument.
+///
+/// ```
+/// #[divan::bench(drop_first = 1)]
+/// fn add() -> i32 {
+///     // ...
+///     # 0
+/// }
+/// ```
+///
+/// This is not the same as warmup. The only warmup Divan does is when tuning
+/// [`sample_size`], which happens before sampling and is skipped entirely if
+/// [`sample_size`] is set. Dropped samples are instead part of sampling: they
+/// use the final sample size and count towards [`sample_count`]. This makes it
+/// useful for excluding outliers caused by one-time costs, such as page faults
+/// on first touch, while still being able to observe those costs by removing
+/// the option.
+///
+/// At least one sample is always kept, so dropping [`sample_count`] or more
+/// samples emits a warning.
+///
 /// ## `threads`
 /// [`threads`]: #threads
 ///
@@ -745,6 +1183,9 @@ pub fn black_box_drop<T>(dummy: T) {
 /// If `min_time > max_time`, then [`max_time`] has priority and [`min_time`]
 /// will not be reached.
 ///
+/// If [`max_time`] is reached before [`sample_count`] samples are collected, a
+/// note is printed after running.
+///
 /// ```
 /// use std::time::Duration;
 ///
@@ -772,6 +1213,28 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// ## `min_samples`
+/// [`min_samples`]: #min_samples
+///
+/// The minimum number of samples collected for each function can be set via
+/// the [`min_samples`] option. This may be overridden at runtime using either
+/// the `DIVAN_MIN_SAMPLES` environment variable or `--min-samples` CLI
+/// argument.
+///
+/// ```
+/// #[divan::bench(max_time = 1, min_samples = 10)]
+/// fn slow() {
+///     // ...
+/// }
+/// ```
+///
+/// This guards statistics against time budgets that leave very slow functions
+/// with only a few samples. It is a hard floor that has priority over
+/// [`max_time`], which is otherwise strict. If [`max_time`] is exceeded to reach
+/// [`min_samples`], the usual note about reaching [`max_time`] is printed after
+/// running. If [`min_samples`] is greater than [`sample_count`], sampling
+/// continues past [`sample_count`] to reach it.
+///
 /// ## `skip_ext_time`
 /// [`skip_ext_time`]: #skip_ext_time
 ///
@@ -806,6 +1269,55 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// ## `max_allocs`
+/// [`max_allocs`]: #max_allocs
+///
+/// Hot paths that are meant to be allocation-free can enforce it via the
+/// [`max_allocs`] option, which fails the benchmark if any iteration makes more
+/// than the given number of heap allocations. This makes accidental
+/// allocations a hard error, such as in CI.
+///
+/// ```
+/// #[divan::bench(max_allocs = 0)]
+/// fn sum(bencher: divan::Bencher) {
+///     let values: Vec<i32> = (0..100).collect();
+///
+///     bencher.bench(|| values.iter().sum::<i32>());
+/// }
+/// ```
+///
+/// Allocations are counted per sample and divided by the sample size, rounding
+/// up, so any allocation within a sample exceeds `max_allocs = 0`. Both `alloc`
+/// and `grow` operations are counted, since growing may move to a new
+/// allocation. Benchmarks that exceed their budget are listed with their
+/// actual count after all benchmarks have run, and then the process exits with
+/// an error.
+///
+/// This requires [`AllocProfiler`] to be the [global allocator](std::alloc::GlobalAlloc),
+/// without which no allocations are counted and the budget is never exceeded.
+/// It is also checked by the single run of `--test`, such as via `cargo test`.
+///
+/// ## `tags`
+/// [`tags`]: #tags
+///
+/// Benchmarks can be labeled via the [`tags`] option, which enables selecting
+/// them across modules using the `--tag` CLI argument or [`Divan::tag`](crate::Divan::tag):
+///
+/// ```
+/// #[divan::bench(tags = ["simd", "hot"])]
+/// fn sum() -> i32 {
+///     // ...
+///     # 0
+/// }
+/// ```
+///
+/// Running with `--tag simd --tag hot` (or `--tag simd,hot`) selects
+/// benchmarks with any of the tags. Adding `--all-tags` selects only benchmarks
+/// with all of them. Tag selection applies in addition to name filters.
+///
+/// Unlike other options, tags are combined with those set by parent
+/// [`#[divan::bench_group]`](macro@bench_group#tags) modules.
+///
 /// ## `ignore`
 /// [`ignore`]: #ignore
 ///
@@ -854,6 +1366,61 @@ pub fn black_box_drop<T>(dummy: T) {
 /// }
 /// ```
 ///
+/// ## `should_panic`
+/// [`should_panic`]: #should_panic
+///
+/// Like [`#[should_panic]`](https://doc.rust-lang.org/reference/attributes/testing.html#the-should_panic-attribute)
+/// for tests, the [`should_panic`] option benchmarks code that is expected to
+/// panic, such as error paths:
+///
+/// ```
+/// #[divan::bench(should_panic)]
+/// fn index_out_of_bounds() -> i32 {
+///     let items: &[i32] = divan::black_box(&[1, 2, 3]);
+///     items[3]
+/// }
+/// ```
+///
+/// Each iteration is run within [`catch_unwind`](std::panic::catch_unwind).
+/// If an iteration does not panic, then the benchmark fails. Expected panics
+/// are not reported by the [panic hook](std::panic::set_hook).
+///
+/// Note that unwinding and catching the panic adds significant overhead to each
+/// iteration, which is included in the measured time. This option thus cannot
+/// be used with [`panic = "abort"`](https://doc.rust-lang.org/cargo/reference/profiles.html#panic).
+///
+/// This option is not supported with [`Bencher`].
+///
+/// ## `black_box_return`
+/// [`black_box_return`]: #black_box_return
+///
+/// By default, the value returned by the benchmarked function is passed through
+/// [`black_box`] and its [drop is deferred](#drop) until after the sample loop.
+/// Setting [`black_box_return`] to `false` instead drops the value immediately
+/// within each iteration, like a function call whose result is unused:
+///
+/// ```
+/// #[divan::bench(black_box_return = false)]
+/// fn unused_sum() -> u64 {
+///     // May be optimized out entirely.
+///     (0..100).sum()
+/// }
+/// ```
+///
+/// This is an escape hatch for measuring what the compiler does with values
+/// that are never used, such as whether a hot path is eliminated as dead code.
+/// It has sharp edges:
+///
+/// - The compiler may remove any work that only produces the returned value,
+///   so the benchmark may measure nothing at all. Use [`black_box`] on inputs
+///   and values that must be computed.
+///
+/// - The time to drop the returned value, such as deallocating a [`String`],
+///   is included in each iteration.
+///
+/// This option is not supported with [`Bencher`], where the closure passed to
+/// [`Bencher::bench`] decides what to return.
+///
 /// [`Any`]: std::any::Any
 /// [`Duration`]: std::time::Duration
 /// [available parallelism]: std::thread::available_parallelism
@@ -943,6 +1510,7 @@ pub use divan_macros::bench;
 /// - [`crate`]
 /// - [`sample_count`]
 /// - [`sample_size`]
+/// - [`drop_first`]
 /// - [`threads`]
 /// - [`counters`]
 ///     - [`bytes_count`]
@@ -950,7 +1518,10 @@ pub use divan_macros::bench;
 ///     - [`items_count`]
 /// - [`min_time`]
 /// - [`max_time`]
+/// - [`min_samples`]
 /// - [`skip_ext_time`]
+/// - [`max_allocs`]
+/// - [`tags`]
 /// - [`ignore`]
 ///
 /// ## `name`
@@ -1009,6 +1580,11 @@ pub use divan_macros::bench;
 /// }
 /// ```
 ///
+/// The runtime override can be limited to benchmarks at or under a path via
+/// `--sample-count <PATH>=<N>`, such as `--sample-count math::fibonacci=1000`.
+/// The most specific path takes precedence, followed by the unscoped
+/// `--sample-count` or `DIVAN_SAMPLE_COUNT`, followed by the attribute option.
+///
 /// If the [`threads`] option is enabled, sample count becomes a multiple of the
 /// number of threads. This is because each thread operates over the same sample
 /// size to ensure there are always N competing threads doing the same amount of
@@ -1020,7 +1596,8 @@ pub use divan_macros::bench;
 /// The number iterations within each statistical sample can be set to a
 /// predetermined [`u32`] value via the [`sample_size`] option. This may be
 /// overridden at runtime using either the `DIVAN_SAMPLE_SIZE` environment
-/// variable or `--sample-size` CLI argument.
+/// variable or `--sample-size` CLI argument. Like [`sample_count`], the runtime
+/// override can be limited to a path via `--sample-size <PATH>=<N>`.
 ///
 /// ```
 /// #[divan::bench_group(sample_size = 1000)]
@@ -1033,6 +1610,11 @@ pub use divan_macros::bench;
 /// }
 /// ```
 ///
+/// ## `drop_first`
+/// [`drop_first`]: #drop_first
+///
+/// See [`#[divan::bench(drop_first = ...)]`](macro@bench#drop_first).
+///
 /// ## `threads`
 /// [`threads`]: #threads
 ///
@@ -1181,6 +1763,9 @@ pub use divan_macros::bench;
 /// If `min_time > max_time`, then [`max_time`] has priority and [`min_time`]
 /// will not be reached.
 ///
+/// If [`max_time`] is reached before [`sample_count`] samples are collected, a
+/// note is printed after running.
+///
 /// ```
 /// use std::time::Duration;
 ///
@@ -1209,6 +1794,11 @@ pub use divan_macros::bench;
 /// }
 /// ```
 ///
+/// ## `min_samples`
+/// [`min_samples`]: #min_samples
+///
+/// See [`#[divan::bench(min_samples = ...)]`](macro@bench#min_samples).
+///
 /// ## `skip_ext_time`
 /// [`skip_ext_time`]: #skip_ext_time
 ///
@@ -1246,6 +1836,29 @@ pub use divan_macros::bench;
 /// }
 /// ```
 ///
+/// ## `max_allocs`
+/// [`max_allocs`]: #max_allocs
+///
+/// See [`#[divan::bench(max_allocs = ...)]`](macro@bench#max_allocs).
+///
+/// ## `tags`
+/// [`tags`]: #tags
+///
+/// Benchmarks in a group can be labeled via the [`tags`] option. Their own
+/// [`tags`](macro@bench#tags) are combined with those of the group.
+///
+/// ```
+/// #[divan::bench_group(tags = ["simd"])]
+/// mod simd {
+///     // Has tags "simd" and "hot".
+///     #[divan::bench(tags = ["hot"])]
+///     fn sum() -> i32 {
+///         // ...
+///         # 0
+///     }
+/// }
+/// ```
+///
 /// ## `ignore`
 /// [`ignore`]: #ignore
 ///
@@ -0,0 +1,127 @@
+//! Raising the scheduling priority of benchmarks via `--priority`.
+//!
+//! With the `priority` crate feature on Unix, the priority of the main thread is
+//! raised for the duration of the run. Threads spawned for benchmarking inherit
+//! it. If the priority cannot be raised, such as without permission, this warns
+//! and benchmarks run at normal priority.
+
+use crate::config::Priority;
+
+/// Restores the previous scheduling priority when dropped.
+pub(crate) struct PriorityGuard {
+    #[cfg(all(feature = "priority", unix, not(miri)))]
+    prev: unix::Prev,
+}
+
+impl PriorityGuard {
+    /// Raises the priority of the current thread for the duration of the
+    /// guard, or warns and returns `None` on failure.
+    pub fn raise(priority: Priority) -> Option<Self> {
+        cfg_if::cfg_if! {
+            if #[cfg(all(feature = "priority", unix, not(miri)))] {
+                match unix::raise(priority) {
+                    Ok(prev) => Some(Self { prev }),
+                    Err(error) => {
+                        eprintln!("warning: Failed to set {} priority ({error}), running at normal priority", priority.name());
+                        if priority == Priority::Realtime {
+                            eprintln!("note: Realtime priority usually requires root or `CAP_SYS_NICE`");
+                        }
+                        None
+                    }
+                }
+            } else if #[cfg(feature = "priority")] {
+                eprintln!("warning: Setting {} priority is not supported on this platform, running at normal priority", priority.name());
+                None
+            } else {
+                eprintln!("warning: Setting {} priority requires the `priority` crate feature, running at normal priority", priority.name());
+                None
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "priority", unix, not(miri)))]
+impl Drop for PriorityGuard {
+    fn drop(&mut self) {
+        unix::restore(&self.prev);
+    }
+}
+
+#[cfg(all(feature = "priority", unix, not(miri)))]
+mod unix {
+    use std::{io, mem};
+
+    use crate::config::Priority;
+
+    /// The niceness used for `--priority high`, which is the highest that
+    /// leaves room above for system services.
+    const HIGH_NICE: libc::c_int = -10;
+
+    pub(super) enum Prev {
+        Nice(libc::c_int),
+        Sched { policy: libc::c_int, param: libc::sched_param },
+    }
+
+    pub(super) fn raise(priority: Priority) -> io::Result<Prev> {
+        match priority {
+            Priority::High => {
+                // `getpriority` can return -1 on success, so errors are only
+                // detected by `setpriority`.
+                //
+                // SAFETY: These calls only affect scheduling of the current
+                // process, or thread on Linux.
+                unsafe {
+                    let prev = libc::getpriority(libc::PRIO_PROCESS, 0);
+
+                    if libc::setpriority(libc::PRIO_PROCESS, 0, HIGH_NICE) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+
+                    Ok(Prev::Nice(prev))
+                }
+            }
+
+            // SCHED_FIFO at its lowest priority is enough to preempt normal
+            // threads without starving kernel threads at higher priorities.
+            //
+            // SAFETY: `sched_param` is zero-initializable and the pointers
+            // passed to libc are valid.
+            Priority::Realtime => unsafe {
+                let thread = libc::pthread_self();
+
+                let mut policy: libc::c_int = 0;
+                let mut param: libc::sched_param = mem::zeroed();
+                let result = libc::pthread_getschedparam(thread, &mut policy, &mut param);
+                if result != 0 {
+                    return Err(io::Error::from_raw_os_error(result));
+                }
+
+                let mut fifo_param: libc::sched_param = mem::zeroed();
+                fifo_param.sched_priority = libc::sched_get_priority_min(libc::SCHED_FIFO);
+
+                let result = libc::pthread_setschedparam(thread, libc::SCHED_FIFO, &fifo_param);
+                if result != 0 {
+                    return Err(io::Error::from_raw_os_error(result));
+                }
+
+                Ok(Prev::Sched { policy, param })
+            },
+        }
+    }
+
+    pub(super) fn restore(prev: &Prev) {
+        // Lowering priority is always permitted, so failures are ignored.
+        //
+        // SAFETY: The previous values were retrieved from libc.
+        unsafe {
+            match *prev {
+                Prev::Nice(nice) => {
+                    libc::setpriority(libc::PRIO_PROCESS, 0, nice);
+                }
+                Prev::Sched { policy, ref param } => {
+                    libc::pthread_setschedparam(libc::pthread_self(), policy, param);
+                }
+            }
+        }
+    }
+}
@@ -12,6 +12,23 @@ pub use crate::{
     time::IntoDuration,
 };
 
+/// Used by `divan::bench_block!` to measure a block in place.
+pub fn bench_block<O>(name: &str, benched: impl FnMut() -> O) -> crate::SampleStats {
+    let stats =
+        crate::measure_standalone(&BenchOptions::default(), |bencher| bencher.bench_local(benched));
+
+    println!(
+        "{name}: {} median, {} fastest, {} slowest ({} samples, {} iters)",
+        stats.time.median,
+        stats.time.fastest,
+        stats.time.slowest,
+        stats.sample_count,
+        stats.iter_count,
+    );
+
+    stats.to_sample_stats()
+}
+
 /// Helper to convert values to strings via `ToString` or fallback to `Debug`.
 ///
 /// This works by having a `Debug`-based `ToString::to_string` method that will
@@ -165,6 +182,52 @@ pub fn new_counter_set() -> crate::counter::CounterSet {
     Default::default()
 }
 
+/// Used by `#[divan::bench(should_panic)]` to catch the panic of each
+/// iteration.
+///
+/// The panic hook is silenced for expected panics so that output is not
+/// flooded with messages. If `f` does not panic, then the benchmark fails.
+#[inline]
+pub fn expect_panic<O>(f: impl FnOnce() -> O) -> Box<dyn any::Any + Send> {
+    use std::{
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+        sync::Once,
+    };
+
+    use self::expecting_panic::EXPECTING_PANIC;
+
+    static INSTALL_HOOK: Once = Once::new();
+
+    INSTALL_HOOK.call_once(|| {
+        let prev_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !EXPECTING_PANIC.with(Cell::get) {
+                prev_hook(info);
+            }
+        }));
+    });
+
+    EXPECTING_PANIC.with(|expecting| expecting.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    EXPECTING_PANIC.with(|expecting| expecting.set(false));
+
+    match result {
+        Ok(_) => panic!("benchmark was expected to panic"),
+        Err(payload) => payload,
+    }
+}
+
+/// Declared outside the scope of `Arg`, whose `get` method would otherwise be
+/// picked up by the `thread_local!` expansion.
+mod expecting_panic {
+    use std::cell::Cell;
+
+    thread_local! {
+        pub static EXPECTING_PANIC: Cell<bool> = const { Cell::new(false) };
+    }
+}
+
 /// Used by `#[divan::bench]` to truncate arrays for generic `const` benchmarks.
 pub const fn shrink_array<T, const IN: usize, const OUT: usize>(
     array: [T; IN],
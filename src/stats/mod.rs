@@ -1,5 +1,7 @@
 //! Measurement statistics.
 
+use std::time::Duration;
+
 use crate::{
     alloc::{AllocOpMap, AllocTally},
     counter::{KnownCounterKind, MaxCountUInt},
@@ -15,24 +17,105 @@ pub(crate) struct Stats {
     /// Total number of samples taken.
     pub sample_count: u32,
 
-    /// Total number of iterations (currently `sample_count * `sample_size`).
+    /// Total number of iterations (currently `sample_count * sample_size`).
     pub iter_count: u64,
 
     /// Timing statistics.
     pub time: StatsSet<FineDuration>,
 
+    /// Per-iteration mean time excluding outlier samples.
+    pub trimmed_mean_time: FineDuration,
+
+    /// Per-iteration interquartile range of times.
+    pub iqr_time: FineDuration,
+
+    /// Per-iteration median absolute deviation of times.
+    pub mad_time: FineDuration,
+
+    /// Per-iteration sample standard deviation of times.
+    pub std_dev_time: FineDuration,
+
+    /// Per-iteration 99th and 99.9th percentile times.
+    pub p99_time: FineDuration,
+    pub p999_time: FineDuration,
+
     /// Allocation statistics associated with the corresponding samples for
     /// `time`.
     pub alloc_tallies: AllocOpMap<AllocTally<StatsSet<f64>>>,
 
     /// `Counter` counts associated with the corresponding samples for `time`.
     pub counts: [Option<StatsSet<MaxCountUInt>>; KnownCounterKind::COUNT],
+
+    /// Timing statistics of generating inputs, if measured via
+    /// `--measure-setup`.
+    pub setup_time: Option<StatsSet<FineDuration>>,
+
+    /// Timing statistics of releasing outputs, if set via
+    /// `Bencher::with_teardown`.
+    pub teardown_time: Option<StatsSet<FineDuration>>,
+
+    /// Median times of the early, middle, and late thirds of samples, if there
+    /// are enough samples.
+    pub drift: Option<[FineDuration; 3]>,
+
+    /// Time of the first call before sampling, if measured via `--show-cold`.
+    pub cold_time: Option<FineDuration>,
 }
 
 impl Stats {
     pub fn get_counts(&self, counter_kind: KnownCounterKind) -> Option<&StatsSet<MaxCountUInt>> {
         self.counts[counter_kind as usize].as_ref()
     }
+
+    pub fn to_sample_stats(&self) -> SampleStats {
+        // Sub-nanosecond precision is truncated by `Duration`.
+        let to_duration = |time: FineDuration| Duration::try_from(time).unwrap_or(Duration::MAX);
+
+        SampleStats {
+            fastest: to_duration(self.time.fastest),
+            slowest: to_duration(self.time.slowest),
+            median: to_duration(self.time.median),
+            mean: to_duration(self.time.mean),
+            std_dev: to_duration(self.std_dev_time),
+            sample_count: self.sample_count,
+            iter_count: self.iter_count,
+        }
+    }
+}
+
+/// Per-iteration timing statistics of a benchmark, returned by
+/// [`Bencher::bench_measured`](crate::Bencher::bench_measured).
+///
+/// This is plain data that can be freely copied and stored. All fields are
+/// always populated, and new fields may be added in minor releases. Times are
+/// truncated to nanosecond precision.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct SampleStats {
+    /// Time of the fastest sample.
+    pub fastest: Duration,
+
+    /// Time of the slowest sample.
+    pub slowest: Duration,
+
+    /// Median time of samples.
+    pub median: Duration,
+
+    /// Mean time of all iterations.
+    pub mean: Duration,
+
+    /// Sample standard deviation of times, or zero if there is only one
+    /// sample.
+    ///
+    /// Unlike `--show-iqr` and `--show-mad`, this is sensitive to rare slow
+    /// samples.
+    pub std_dev: Duration,
+
+    /// Number of samples taken.
+    pub sample_count: u32,
+
+    /// Number of iterations across all samples.
+    pub iter_count: u64,
 }
 
 #[derive(Debug)]
@@ -55,3 +138,134 @@ impl StatsSet<f64> {
         self.fastest == 0.0 && self.slowest == 0.0 && self.median == 0.0 && self.mean == 0.0
     }
 }
+
+/// Running mean and variance via Welford's online algorithm.
+///
+/// The mean is updated by each value's difference from it, rather than by
+/// summing values and their squares. This avoids the catastrophic cancellation
+/// of subtracting large sums, and intermediate values stay on the order of the
+/// inputs and their squared differences. For values up to [`FineDuration::MAX`]
+/// picoseconds, results are finite and never NaN.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct RunningStats {
+    count: u64,
+    mean: f64,
+
+    /// Sum of squared differences from the mean.
+    m2: f64,
+}
+
+impl RunningStats {
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Returns the mean of values, or 0 if there are none.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the sample variance of values, or 0 if there are fewer than 2.
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+
+        self.m2 / (self.count - 1) as f64
+    }
+}
+
+/// Returns the sample standard deviation of `times` as a percentage of their
+/// mean, or 0 if the mean is 0.
+pub(crate) fn relative_std_dev(times: &[FineDuration]) -> f64 {
+    let mut stats = RunningStats::default();
+    for time in times {
+        stats.push(time.picos as f64);
+    }
+
+    if stats.mean() == 0.0 {
+        return 0.0;
+    }
+
+    stats.variance().sqrt() / stats.mean() * 100.0
+}
+
+/// Computes the geometric mean of `times`, or `None` if there are none.
+///
+/// Unlike the arithmetic mean, this is not dominated by the slowest times, so
+/// each time affects the result by its relative rather than absolute size.
+/// Zero times are treated as 1 picosecond to stay within the domain of `ln`.
+pub(crate) fn geometric_mean(times: &[FineDuration]) -> Option<FineDuration> {
+    if times.is_empty() {
+        return None;
+    }
+
+    let ln_sum: f64 = times.iter().map(|time| (time.picos.max(1) as f64).ln()).sum();
+    let picos = (ln_sum / times.len() as f64).exp();
+
+    Some(FineDuration { picos: picos.round() as u128 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometric_mean() {
+        let mean = |picos: &[u128]| {
+            let times: Vec<_> = picos.iter().map(|&picos| FineDuration { picos }).collect();
+            super::geometric_mean(&times).map(|time| time.picos)
+        };
+
+        assert_eq!(mean(&[]), None);
+        assert_eq!(mean(&[7]), Some(7));
+        assert_eq!(mean(&[2, 8]), Some(4));
+
+        // A 1000x slower time moves the result as much as a 1000x faster one.
+        assert_eq!(mean(&[1_000, 1_000_000]), Some(31_623));
+        assert_eq!(mean(&[1_000, 1_000_000, 1_000_000_000]), Some(1_000_000));
+    }
+
+    #[test]
+    fn running_stats() {
+        let stats = |values: &[f64]| {
+            let mut stats = RunningStats::default();
+            values.iter().for_each(|&value| stats.push(value));
+            stats
+        };
+
+        assert_eq!(stats(&[]).mean(), 0.0);
+        assert_eq!(stats(&[]).variance(), 0.0);
+        assert_eq!(stats(&[3.0]).variance(), 0.0);
+
+        let small = stats(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(small.mean(), 5.0);
+        assert_eq!(small.variance(), 32.0 / 7.0);
+
+        // A large offset does not cancel out the variance, unlike with sums of
+        // squares.
+        let offset = stats(&[1e12 + 2.0, 1e12 + 4.0, 1e12 + 6.0]);
+        assert_eq!(offset.variance(), 4.0);
+    }
+
+    #[test]
+    fn huge_times() {
+        let max = FineDuration::MAX;
+        let zero = FineDuration::default();
+
+        for times in [&[max, max][..], &[zero, max], &[max, zero, max, zero], &[max; 1000]] {
+            let rsd = relative_std_dev(times);
+            assert!(rsd.is_finite(), "{rsd}");
+
+            let geomean = super::geometric_mean(times).unwrap();
+            assert!(geomean <= max);
+        }
+
+        assert_eq!(relative_std_dev(&[max; 1000]), 0.0);
+        assert_eq!(relative_std_dev(&[zero; 10]), 0.0);
+    }
+}
@@ -1,11 +1,19 @@
 use std::collections::HashMap;
 
 use crate::{
-    alloc::ThreadAllocTallyMap,
+    alloc::{AllocOp, ThreadAllocTallyMap},
     counter::KnownCounterKind,
+    stats::StatsSet,
     time::{FineDuration, Timer, Timestamp},
+    util,
 };
 
+/// The minimum number of samples for drift analysis to be meaningful.
+pub(crate) const MIN_DRIFT_SAMPLES: usize = 30;
+
+/// The percentage of samples excluded from each end for the trimmed mean.
+pub(crate) const TRIM_PERCENT: usize = 10;
+
 /// Timing measurement.
 pub(crate) struct TimeSample {
     /// The time this sample took to run.
@@ -25,6 +33,10 @@ pub(crate) struct RawSample {
     pub timer: Timer,
     pub alloc_tallies: ThreadAllocTallyMap,
     pub counter_totals: [u128; KnownCounterKind::COUNT],
+
+    /// Time spent releasing outputs via `Bencher::with_teardown`, after the
+    /// timed section.
+    pub teardown_time: Option<FineDuration>,
 }
 
 /// Multi-thread measurement.
@@ -84,12 +96,6 @@ impl SampleCollection {
         self.sample_size as u64 * self.time_samples.len() as u64
     }
 
-    /// Computes the total time across all samples.
-    #[inline]
-    pub fn total_duration(&self) -> FineDuration {
-        FineDuration { picos: self.time_samples.iter().map(|s| s.duration.picos).sum() }
-    }
-
     /// Returns all samples sorted by duration.
     #[inline]
     pub fn sorted_samples(&self) -> Vec<&TimeSample> {
@@ -97,4 +103,357 @@ impl SampleCollection {
         result.sort_unstable_by_key(|s| s.duration);
         result
     }
+
+    /// Keeps allocation counts of the single run of `--test`, which records no
+    /// time samples, so that `max_allocs` is still enforced.
+    ///
+    /// When testing multiple threads, only the current thread is counted.
+    pub fn insert_test_alloc_tallies(&mut self, alloc_tallies: ThreadAllocTallyMap) {
+        if !alloc_tallies.is_empty() {
+            self.alloc_tallies.insert(0, alloc_tallies);
+        }
+    }
+
+    /// Computes the most allocations of any sample divided by sample size,
+    /// rounded up so that any allocation within a sample counts.
+    ///
+    /// Both `alloc` and `grow` operations are counted, since growing may move
+    /// to a new allocation.
+    pub fn max_allocs_per_iter(&self) -> u64 {
+        let max_count = self
+            .alloc_tallies
+            .values()
+            .map(|tallies| {
+                tallies.get(AllocOp::Alloc).count as u128 + tallies.get(AllocOp::Grow).count as u128
+            })
+            .max()
+            .unwrap_or_default();
+
+        let max_per_iter = max_count.div_ceil(u128::from(self.sample_size.max(1)));
+        u64::try_from(max_per_iter).unwrap_or(u64::MAX)
+    }
+
+    /// Computes the per-iteration median time of each third of samples in the
+    /// order they were collected.
+    ///
+    /// Returns `None` if there are fewer than [`MIN_DRIFT_SAMPLES`].
+    pub fn drift(&self) -> Option<[FineDuration; 3]> {
+        let samples = &self.time_samples;
+        if samples.len() < MIN_DRIFT_SAMPLES {
+            return None;
+        }
+
+        let third = samples.len() / 3;
+        let thirds = [
+            &samples[..third],
+            &samples[third..samples.len() - third],
+            &samples[samples.len() - third..],
+        ];
+
+        Some(thirds.map(|samples| {
+            let mut sorted: Vec<FineDuration> = samples.iter().map(|s| s.duration).collect();
+            sorted.sort_unstable();
+
+            let picos: Vec<u128> = sorted.iter().map(|d| d.picos).collect();
+
+            FineDuration { picos: median_picos(&picos) } / self.sample_size
+        }))
+    }
+
+    /// Computes the per-iteration mean time, excluding the fastest and slowest
+    /// [`TRIM_PERCENT`] of samples.
+    pub fn trimmed_mean_time(&self) -> FineDuration {
+        let sorted_samples = self.sorted_samples();
+
+        let trim = sorted_samples.len() * TRIM_PERCENT / 100;
+        let kept = &sorted_samples[trim..sorted_samples.len() - trim];
+
+        let count = kept.len() as u128 * self.sample_size as u128;
+
+        FineDuration { picos: mean_picos(kept.iter().map(|s| s.duration.picos), count) }
+    }
+
+    /// Computes the per-iteration interquartile range: the spread between the
+    /// medians of the faster and slower halves of samples.
+    pub fn iqr_time(&self) -> FineDuration {
+        let sorted: Vec<u128> = self.sorted_samples().iter().map(|s| s.duration.picos).collect();
+
+        let len = sorted.len();
+        let q1 = median_picos(&sorted[..len / 2]);
+        let q3 = median_picos(&sorted[len.div_ceil(2)..]);
+
+        FineDuration { picos: q3 - q1 } / self.sample_size.max(1)
+    }
+
+    /// Computes the per-iteration time that `per_mille` thousandths of samples
+    /// are at or below, such as 999 for p99.9, using the nearest rank.
+    pub fn quantile_time(&self, per_mille: usize) -> FineDuration {
+        let sorted_samples = self.sorted_samples();
+
+        let rank = (sorted_samples.len() * per_mille).div_ceil(1000).max(1);
+        match sorted_samples.get(rank - 1).or(sorted_samples.last()) {
+            Some(sample) => sample.duration / self.sample_size.max(1),
+            None => FineDuration::default(),
+        }
+    }
+
+    /// Computes the per-iteration median absolute deviation: the median
+    /// distance of samples from the median sample.
+    pub fn mad_time(&self) -> FineDuration {
+        let sorted: Vec<u128> = self.sorted_samples().iter().map(|s| s.duration.picos).collect();
+        let median = median_picos(&sorted);
+
+        let mut deviations: Vec<u128> =
+            sorted.iter().map(|&picos| picos.abs_diff(median)).collect();
+        deviations.sort_unstable();
+
+        FineDuration { picos: median_picos(&deviations) } / self.sample_size.max(1)
+    }
+
+    /// Computes the per-iteration sample standard deviation of times.
+    pub fn std_dev_time(&self) -> FineDuration {
+        let mut stats = crate::stats::RunningStats::default();
+        for sample in &self.time_samples {
+            stats.push(sample.duration.picos as f64);
+        }
+
+        let picos = stats.variance().sqrt() / f64::from(self.sample_size.max(1));
+        FineDuration { picos: picos.round() as u128 }
+    }
+
+    /// Computes per-iteration timing statistics.
+    pub fn time_stats(&self) -> StatsSet<FineDuration> {
+        let sample_size = self.sample_size;
+
+        let total_count = self.iter_count();
+
+        let sorted_samples = self.sorted_samples();
+        let sorted_picos: Vec<u128> = sorted_samples.iter().map(|s| s.duration.picos).collect();
+
+        let median = if sorted_picos.is_empty() {
+            FineDuration::default()
+        } else {
+            FineDuration { picos: median_picos(&sorted_picos) } / sample_size
+        };
+
+        StatsSet {
+            fastest: sorted_samples.first().map(|s| s.duration / sample_size).unwrap_or_default(),
+            slowest: sorted_samples.last().map(|s| s.duration / sample_size).unwrap_or_default(),
+            median,
+            mean: FineDuration {
+                picos: mean_picos(sorted_picos.iter().copied(), total_count as u128),
+            },
+        }
+    }
+}
+
+/// Returns the median of sorted `picos`, or 0 if empty.
+fn median_picos(sorted: &[u128]) -> u128 {
+    let middle = util::slice_middle(sorted);
+    mean_picos(middle.iter().copied(), middle.len() as u128)
+}
+
+/// Returns the sum of `picos` divided by `divisor`, rounded down, or 0 if
+/// `divisor` is 0.
+///
+/// Summing picoseconds can overflow `u128` for very long samples, so this
+/// instead sums the quotient and remainder of dividing each value by
+/// `divisor`. Remainders are carried into the quotient as they reach
+/// `divisor`, so neither sum overflows if there are at most `divisor` values,
+/// and the result is exact.
+fn mean_picos(picos: impl IntoIterator<Item = u128>, divisor: u128) -> u128 {
+    if divisor == 0 {
+        return 0;
+    }
+
+    let mut quotient: u128 = 0;
+    let mut remainder: u128 = 0;
+
+    for picos in picos {
+        quotient += picos / divisor;
+
+        // Equivalent to `remainder + picos % divisor >= divisor` without
+        // overflowing.
+        let picos_remainder = picos % divisor;
+        if picos_remainder >= divisor - remainder {
+            quotient += 1;
+            remainder = picos_remainder - (divisor - remainder);
+        } else {
+            remainder += picos_remainder;
+        }
+    }
+
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection(picos: &[u128]) -> SampleCollection {
+        SampleCollection {
+            sample_size: 2,
+            time_samples: picos
+                .iter()
+                .map(|&picos| TimeSample { duration: FineDuration { picos } })
+                .collect(),
+            ..SampleCollection::default()
+        }
+    }
+
+    #[test]
+    fn max_allocs_per_iter() {
+        let mut collection = collection(&[10, 10, 10]);
+        assert_eq!(collection.max_allocs_per_iter(), 0);
+
+        let tallies = |alloc: u64, grow: u64, dealloc: u64| {
+            let mut tallies = ThreadAllocTallyMap::new();
+            tallies.get_mut(AllocOp::Alloc).count = alloc as _;
+            tallies.get_mut(AllocOp::Grow).count = grow as _;
+            tallies.get_mut(AllocOp::Dealloc).count = dealloc as _;
+            tallies
+        };
+
+        // Deallocations are not counted.
+        collection.alloc_tallies.insert(0, tallies(0, 0, 9));
+        assert_eq!(collection.max_allocs_per_iter(), 0);
+
+        // A single allocation in a sample of 2 iterations rounds up.
+        collection.alloc_tallies.insert(1, tallies(1, 0, 0));
+        assert_eq!(collection.max_allocs_per_iter(), 1);
+
+        collection.alloc_tallies.insert(2, tallies(3, 2, 0));
+        assert_eq!(collection.max_allocs_per_iter(), 3);
+    }
+
+    #[test]
+    fn drift() {
+        assert_eq!(collection(&[10; MIN_DRIFT_SAMPLES - 1]).drift(), None);
+
+        // Thirds are in collection order rather than sorted.
+        let picos: Vec<u128> = (0..MIN_DRIFT_SAMPLES as u128).rev().map(|i| i * 2).collect();
+        let [early, middle, late] = collection(&picos).drift().unwrap();
+        assert_eq!([early.picos, middle.picos, late.picos], [24, 14, 4]);
+    }
+
+    #[test]
+    fn trimmed_mean_time() {
+        assert_eq!(collection(&[]).trimmed_mean_time(), FineDuration::default());
+
+        // Too few samples to trim.
+        assert_eq!(collection(&[2, 4, 6]).trimmed_mean_time().picos, 2);
+
+        // Outliers at both ends are excluded, unlike with the mean.
+        let mut picos = vec![4; 8];
+        picos.extend([0, 1000]);
+        let collection = collection(&picos);
+        assert_eq!(collection.trimmed_mean_time().picos, 2);
+        assert_eq!(collection.time_stats().mean.picos, 51);
+    }
+
+    #[test]
+    fn spread_time() {
+        assert_eq!(collection(&[]).iqr_time(), FineDuration::default());
+        assert_eq!(collection(&[]).mad_time(), FineDuration::default());
+        assert_eq!(collection(&[]).std_dev_time(), FineDuration::default());
+        assert_eq!(collection(&[7, 7, 7]).std_dev_time(), FineDuration::default());
+
+        let collection = collection(&[14, 2, 200, 8, 12, 4, 10, 6]);
+        assert_eq!(collection.iqr_time().picos, 4);
+        assert_eq!(collection.mad_time().picos, 2);
+    }
+
+    #[test]
+    fn spread_time_skewed() {
+        // Mostly steady samples with a rare slow tail, as from interrupts.
+        let skewed = |tail: u128| {
+            let picos: Vec<u128> =
+                (0..100).map(|i| if i % 20 == 0 { tail } else { 100 + i }).collect();
+            collection(&picos)
+        };
+
+        let std_dev = |collection: &SampleCollection| collection.std_dev_time().picos;
+
+        let short_tail = skewed(1_000);
+        let long_tail = skewed(1_000_000);
+
+        // The tail dominates the standard deviation.
+        assert!(std_dev(&long_tail) > 1000 * std_dev(&short_tail));
+
+        // The spread of typical samples is unaffected by how slow the tail is.
+        assert_eq!(short_tail.iqr_time(), long_tail.iqr_time());
+        assert_eq!(short_tail.mad_time(), long_tail.mad_time());
+        assert_eq!(long_tail.mad_time().picos, 13);
+    }
+
+    #[test]
+    fn quantile_time() {
+        assert_eq!(collection(&[]).quantile_time(990), FineDuration::default());
+        assert_eq!(collection(&[10]).quantile_time(999).picos, 5);
+
+        // 1 through 1000 per iteration in reverse, so ranks must come from
+        // sorting.
+        let picos: Vec<u128> = (1..=1000).rev().map(|n| n * 2).collect();
+        let collection = collection(&picos);
+        assert_eq!(collection.quantile_time(500).picos, 500);
+        assert_eq!(collection.quantile_time(990).picos, 990);
+        assert_eq!(collection.quantile_time(999).picos, 999);
+        assert_eq!(collection.quantile_time(1000).picos, 1000);
+        assert_eq!(collection.quantile_time(0).picos, 1);
+    }
+
+    #[test]
+    fn huge_times() {
+        let max = FineDuration::MAX.picos;
+
+        // Sums of these would overflow.
+        let collection = SampleCollection {
+            sample_size: 1,
+            time_samples: [max, max - 1, max, max - 3]
+                .into_iter()
+                .map(|picos| TimeSample { duration: FineDuration { picos } })
+                .collect(),
+            ..SampleCollection::default()
+        };
+
+        let stats = collection.time_stats();
+        assert_eq!(stats.fastest.picos, max - 3);
+        assert_eq!(stats.slowest.picos, max);
+        assert_eq!(stats.median.picos, max - 1);
+        assert_eq!(stats.mean.picos, max - 1);
+        assert_eq!(collection.trimmed_mean_time().picos, max - 1);
+        assert_eq!(collection.iqr_time().picos, 2);
+        assert_eq!(collection.mad_time().picos, 1);
+
+        // Exact division of totals that do not fit in `u128`.
+        assert_eq!(mean_picos([max; 3], 3), max);
+        assert_eq!(mean_picos([max, max - 1], 2), max - 1);
+        assert_eq!(mean_picos([max, max], max), 2);
+    }
+
+    #[test]
+    fn fastest_time() {
+        // Noise only ever adds time, so the floor is the uninterrupted cost.
+        let noisy = |sample_size: u32| {
+            let mut rng = crate::util::rand::Rng::new(u64::from(sample_size));
+            let time_samples = (0..100)
+                .map(|_| {
+                    let noise =
+                        if rng.gen_index(10) == 0 { rng.gen_index(1000) as u128 } else { 0 };
+                    let picos = 10 * sample_size as u128 + noise;
+                    TimeSample { duration: FineDuration { picos } }
+                })
+                .chain([TimeSample { duration: FineDuration { picos: 10 * sample_size as u128 } }])
+                .collect();
+
+            SampleCollection { sample_size, time_samples, ..SampleCollection::default() }
+        };
+
+        // Normalized per iteration to be comparable across sample sizes.
+        for sample_size in [1, 8, 1000] {
+            let stats = noisy(sample_size).time_stats();
+            assert_eq!(stats.fastest.picos, 10);
+            assert!(stats.fastest <= stats.median && stats.median <= stats.mean);
+        }
+    }
 }
@@ -0,0 +1,66 @@
+//! Timing-free listings of benchmarks for `--snapshot-structure` and
+//! `--check-structure`.
+
+use std::collections::HashSet;
+
+/// Formats benchmark paths as a listing with one path per line.
+pub(crate) fn listing(paths: &[String]) -> String {
+    paths.iter().map(|path| format!("{path}\n")).collect()
+}
+
+/// Returns lines describing how the `actual` listing differs from `expected`,
+/// which is empty if they are the same.
+///
+/// Removed paths are prefixed with `-` and added paths with `+`. If both
+/// listings have the same paths in a different order, a single line says so.
+pub(crate) fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().filter(|line| !line.is_empty()).collect();
+    let actual: Vec<&str> = actual.lines().filter(|line| !line.is_empty()).collect();
+
+    if expected == actual {
+        return Vec::new();
+    }
+
+    let expected_set: HashSet<&str> = expected.iter().copied().collect();
+    let actual_set: HashSet<&str> = actual.iter().copied().collect();
+
+    let removed = expected.iter().filter(|path| !actual_set.contains(*path));
+    let added = actual.iter().filter(|path| !expected_set.contains(*path));
+
+    let mut result: Vec<String> = removed
+        .map(|path| format!("- {path}"))
+        .chain(added.map(|path| format!("+ {path}")))
+        .collect();
+
+    if result.is_empty() {
+        result.push("~ benchmarks are in a different order".to_owned());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_listings() {
+        let listing = "math::add\nmath::fibonacci::1\nmath::fibonacci::2\n";
+
+        assert!(diff(listing, listing).is_empty());
+
+        // Line endings are not significant.
+        assert!(diff(listing, &listing.replace('\n', "\r\n")).is_empty());
+        assert!(diff(listing, listing.trim_end()).is_empty());
+
+        assert_eq!(
+            diff(listing, "math::add\nmath::fibonacci::2\nmath::sub\n"),
+            ["- math::fibonacci::1", "+ math::sub"],
+        );
+
+        assert_eq!(
+            diff(listing, "math::fibonacci::1\nmath::fibonacci::2\nmath::add\n"),
+            ["~ benchmarks are in a different order"],
+        );
+    }
+}
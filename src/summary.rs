@@ -0,0 +1,410 @@
+//! Digests of benchmark results printed after a run.
+
+use std::cmp::Ordering;
+
+use crate::{
+    bench::PhaseTimes,
+    config::PrimaryStat,
+    counter::{AnyCounter, BytesFormat, KnownCounterKind, MaxCountUInt},
+    stats::{self, Stats, MIN_DRIFT_SAMPLES},
+    time::FineDuration,
+};
+
+/// Results of benchmarks collected for printing after a run.
+pub(crate) struct Summary {
+    /// The statistic that `time` and `counts` of entries are taken from.
+    stat: PrimaryStat,
+
+    entries: Vec<SummaryEntry>,
+
+    /// Time spent in each phase across all benchmarks, for
+    /// `--time-breakdown`.
+    phase_times: PhaseTimes,
+}
+
+struct SummaryEntry {
+    /// Path components of the benchmark, including arguments and thread
+    /// counts.
+    path: Vec<String>,
+
+    time: FineDuration,
+
+    counts: [Option<MaxCountUInt>; KnownCounterKind::COUNT],
+
+    /// Median times of the early, middle, and late thirds of samples.
+    drift: Option<[FineDuration; 3]>,
+
+    /// Times of each separate run from `--stress`, in run order. Empty if the
+    /// benchmark was run once.
+    stress_times: Vec<FineDuration>,
+}
+
+impl SummaryEntry {
+    /// Returns the number of counted values processed per picosecond.
+    fn throughput(&self, counter_kind: KnownCounterKind) -> Option<f64> {
+        let count = self.counts[counter_kind as usize]?;
+        Some(count as f64 / self.time.picos as f64)
+    }
+}
+
+/// Options for printing a [`Summary`].
+pub(crate) struct SummaryFormat<'a> {
+    pub path_separator: &'a str,
+    pub bytes_format: BytesFormat,
+}
+
+impl Summary {
+    pub fn new(stat: PrimaryStat) -> Self {
+        Self { stat, entries: Vec::new(), phase_times: PhaseTimes::default() }
+    }
+
+    pub fn push(&mut self, path: Vec<String>, stats: &Stats) {
+        let time = self.time_of(stats);
+
+        // Counts rarely vary between samples, so trimming is not worthwhile.
+        let counts = KnownCounterKind::ALL.map(|counter_kind| {
+            let counts = stats.get_counts(counter_kind)?;
+            Some(match self.stat {
+                PrimaryStat::Median | PrimaryStat::TrimmedMean => counts.median,
+                PrimaryStat::Mean => counts.mean,
+                PrimaryStat::Fastest => counts.fastest,
+            })
+        });
+
+        self.entries.push(SummaryEntry {
+            path,
+            time,
+            counts,
+            drift: stats.drift,
+            stress_times: Vec::new(),
+        });
+    }
+
+    /// Accounts time spent in each phase of running a benchmark.
+    pub fn add_phase_times(&mut self, phase_times: PhaseTimes) {
+        self.phase_times += phase_times;
+    }
+
+    /// Pushes a benchmark that was run multiple times via `--stress`. The last
+    /// run is used for other digests.
+    pub fn push_runs(&mut self, path: Vec<String>, runs: &[Stats]) {
+        let Some(last_run) = runs.last() else {
+            return;
+        };
+
+        let stress_times = runs.iter().map(|stats| self.time_of(stats)).collect();
+
+        self.push(path, last_run);
+
+        if let Some(entry) = self.entries.last_mut() {
+            entry.stress_times = stress_times;
+        }
+    }
+
+    fn time_of(&self, stats: &Stats) -> FineDuration {
+        match self.stat {
+            PrimaryStat::Median => stats.time.median,
+            PrimaryStat::Mean => stats.time.mean,
+            PrimaryStat::TrimmedMean => stats.trimmed_mean_time,
+            PrimaryStat::Fastest => stats.time.fastest,
+        }
+    }
+
+    /// Prints ranked lists of the `top` benchmarks by time, followed by
+    /// throughput for each counter used.
+    pub fn print_ranked(&self, top: usize, format: &SummaryFormat) {
+        if self.entries.is_empty() || top == 0 {
+            return;
+        }
+
+        let mut by_time: Vec<&SummaryEntry> = self.entries.iter().collect();
+        by_time.sort_by_key(|entry| entry.time);
+
+        let print_list = |title: &str,
+                          entries: &mut dyn Iterator<Item = &SummaryEntry>,
+                          counter_kind: Option<KnownCounterKind>| {
+            let rows = entries.take(top).map(|entry| {
+                let value = match counter_kind {
+                    None => entry.time.to_string(),
+                    Some(counter_kind) => {
+                        let count = entry.counts[counter_kind as usize].unwrap_or_default();
+                        AnyCounter::known(counter_kind, count)
+                            .display_throughput(entry.time, format.bytes_format)
+                            .to_string()
+                    }
+                };
+                (entry, [value])
+            });
+
+            print_table(&format!("{title} by {}:", self.stat.name()), rows, format);
+        };
+
+        print_list("Fastest", &mut by_time.iter().copied(), None);
+        print_list("Slowest", &mut by_time.iter().rev().copied(), None);
+
+        for counter_kind in KnownCounterKind::ALL {
+            let mut by_throughput: Vec<(&SummaryEntry, f64)> = self
+                .entries
+                .iter()
+                .filter_map(|entry| Some((entry, entry.throughput(counter_kind)?)))
+                .collect();
+
+            if by_throughput.is_empty() {
+                continue;
+            }
+
+            by_throughput.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+            let entries = || by_throughput.iter().map(|&(entry, _)| entry);
+
+            let name = counter_kind.name();
+            print_list(&format!("Highest {name} throughput"), &mut entries(), Some(counter_kind));
+            print_list(
+                &format!("Lowest {name} throughput"),
+                &mut entries().rev(),
+                Some(counter_kind),
+            );
+        }
+    }
+
+    /// Prints the time of each benchmark as a multiple of the time of the
+    /// benchmark at `reference_path`.
+    ///
+    /// Returns `false` if no benchmark matches `reference_path`.
+    pub fn print_relative(&self, reference_path: &str, format: &SummaryFormat) -> bool {
+        let Some(reference) =
+            self.entries.iter().find(|entry| entry.path.join("::") == reference_path)
+        else {
+            return false;
+        };
+
+        // Times can be zero after subtracting sample loop overhead, which
+        // would make every ratio infinite.
+        if reference.time.picos == 0 {
+            eprintln!(
+                "warning: Cannot show times relative to '{reference_path}' because its {} time is 0",
+                self.stat.name(),
+            );
+            return true;
+        }
+
+        let reference_secs = reference.time.as_secs_f64();
+
+        let rows = self.entries.iter().map(|entry| {
+            let ratio = entry.time.as_secs_f64() / reference_secs;
+            (entry, [format!("{ratio:.2}x"), entry.time.to_string()])
+        });
+
+        let title = format!("Relative to {reference_path} by {}:", self.stat.name());
+        print_table(&title, rows, format);
+        true
+    }
+
+    /// Prints the median time of the early, middle, and late thirds of each
+    /// benchmark's samples, flagging benchmarks that consistently got slower or
+    /// faster over the run.
+    pub fn print_drift(&self, format: &SummaryFormat) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let rows = self.entries.iter().filter_map(|entry| {
+            let [early, middle, late] = entry.drift?;
+
+            // Times can be zero after subtracting sample loop overhead, in
+            // which case there is no relative change to report.
+            let change = if early.picos == 0 {
+                None
+            } else {
+                Some((late.as_secs_f64() / early.as_secs_f64() - 1.0) * 100.0)
+            };
+
+            let is_monotonic =
+                (early < middle && middle < late) || (early > middle && middle > late);
+            let is_drifting = is_monotonic
+                && change.is_some_and(|change| change.abs() >= DRIFT_THRESHOLD_PERCENT);
+
+            Some((
+                entry,
+                [
+                    early.to_string(),
+                    middle.to_string(),
+                    late.to_string(),
+                    change.map(|change| format!("{change:+.1}%")).unwrap_or_default(),
+                    if is_drifting { "drifting" } else { "" }.to_owned(),
+                ],
+            ))
+        });
+
+        let rows: Vec<_> = rows.collect();
+
+        if rows.is_empty() {
+            eprintln!(
+                "note: Drift analysis requires benchmarks with at least {MIN_DRIFT_SAMPLES} samples"
+            );
+            return;
+        }
+
+        print_table("Drift by early, middle, and late samples:", rows.into_iter(), format);
+    }
+
+    /// Prints benchmarks grouped into tiers of time separated by `bounds`,
+    /// which must be sorted.
+    pub fn print_tiers(&self, bounds: &[FineDuration], format: &SummaryFormat) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let mut by_time: Vec<&SummaryEntry> = self.entries.iter().collect();
+        by_time.sort_by_key(|entry| entry.time);
+
+        for tier in 0..=bounds.len() {
+            let lower = tier.checked_sub(1).map(|i| bounds[i]);
+            let upper = bounds.get(tier).copied();
+
+            let range = match (lower, upper) {
+                (None, Some(upper)) => format!("Under {upper}"),
+                (Some(lower), Some(upper)) => format!("{lower} to {upper}"),
+                (Some(lower), None) => format!("{lower} and over"),
+                (None, None) => "All".to_owned(),
+            };
+
+            let entries: Vec<&SummaryEntry> = by_time
+                .iter()
+                .copied()
+                .filter(|entry| {
+                    lower.unwrap_or_default() <= entry.time
+                        && entry.time < upper.unwrap_or(FineDuration::MAX)
+                })
+                .collect();
+
+            let title = format!("{range} by {} ({}):", self.stat.name(), entries.len());
+            let rows = entries.into_iter().map(|entry| (entry, [entry.time.to_string()]));
+            print_table(&title, rows, format);
+        }
+    }
+
+    /// Prints the spread of times across separate runs of each benchmark from
+    /// `--stress`, led by the relative standard deviation.
+    pub fn print_stress(&self, format: &SummaryFormat) {
+        let rows: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.stress_times.len() > 1)
+            .map(|entry| {
+                let mut times = entry.stress_times.clone();
+                times.sort_unstable();
+
+                let min = times[0];
+                let max = times[times.len() - 1];
+                let median = times[times.len() / 2];
+
+                (
+                    entry,
+                    [
+                        format!("{:.2}% RSD", stats::relative_std_dev(&times)),
+                        format!("min {min}"),
+                        format!("median {median}"),
+                        format!("max {max}"),
+                    ],
+                )
+            })
+            .collect();
+
+        let Some(runs) = rows.first().map(|(entry, _)| entry.stress_times.len()) else {
+            return;
+        };
+
+        print_table(
+            &format!("Spread of {} across {runs} runs:", self.stat.name()),
+            rows.into_iter(),
+            format,
+        );
+    }
+
+    /// Prints the geometric mean of times across all benchmarks, as a single
+    /// headline figure for the run.
+    pub fn print_geomean(&self) {
+        let times: Vec<FineDuration> = self.entries.iter().map(|entry| entry.time).collect();
+
+        let Some(geomean) = stats::geometric_mean(&times) else {
+            return;
+        };
+
+        let count = times.len();
+        let plural = if count == 1 { "" } else { "s" };
+
+        println!(
+            "Geometric mean of {} across {count} benchmark{plural}: {geomean}",
+            self.stat.name()
+        );
+        println!();
+    }
+
+    /// Prints where the `total` time of the run went. Time outside of warmup,
+    /// sampling, and setup measurement is attributed to overhead, such as
+    /// computing statistics and printing.
+    pub fn print_time_breakdown(&self, total: FineDuration) {
+        let PhaseTimes { warmup, sampling, setup } = self.phase_times;
+
+        let overhead = FineDuration {
+            picos: total.picos.saturating_sub(warmup.picos + sampling.picos + setup.picos),
+        };
+
+        let rows =
+            [("sampling", sampling), ("warmup", warmup), ("setup", setup), ("overhead", overhead)]
+                .map(|(name, time)| {
+                    let percent = if total.picos == 0 {
+                        0.0
+                    } else {
+                        time.picos as f64 / total.picos as f64 * 100.0
+                    };
+                    (name, time.to_string(), format!("{percent:.1}%"))
+                });
+
+        let time_width = rows.iter().map(|(_, time, _)| time.chars().count()).max().unwrap_or(0);
+
+        println!("Time breakdown:");
+
+        for (name, time, percent) in &rows {
+            println!("  {name:<8}  {time:>time_width$}  {percent:>6}");
+        }
+
+        println!("  {:<8}  {:>time_width$}", "total", total.to_string());
+        println!();
+    }
+}
+
+/// The minimum change between early and late samples for consistent drift to
+/// be flagged.
+const DRIFT_THRESHOLD_PERCENT: f64 = 5.0;
+
+/// Prints a numbered table of benchmark paths with values in aligned columns.
+fn print_table<'a, const N: usize>(
+    title: &str,
+    rows: impl Iterator<Item = (&'a SummaryEntry, [String; N])>,
+    format: &SummaryFormat,
+) {
+    let rows: Vec<(String, [String; N])> =
+        rows.map(|(entry, values)| (entry.path.join(format.path_separator), values)).collect();
+
+    let path_width = rows.iter().map(|(path, _)| path.chars().count()).max().unwrap_or_default();
+
+    let value_widths: [usize; N] = std::array::from_fn(|i| {
+        rows.iter().map(|(_, values)| values[i].chars().count()).max().unwrap_or_default()
+    });
+
+    println!("{title}");
+
+    for (i, (path, values)) in rows.iter().enumerate() {
+        let mut line = format!("{:>4}. {path:path_width$}", i + 1);
+
+        for (value, &width) in values.iter().zip(&value_widths) {
+            line.push_str(&format!("  {value:>width$}"));
+        }
+
+        println!("{}", line.trim_end());
+    }
+
+    println!();
+}
@@ -1,4 +1,4 @@
-use std::{fmt, ops, time::Duration};
+use std::{fmt, num::TryFromIntError, ops, time::Duration};
 
 use crate::util;
 
@@ -9,6 +9,9 @@ pub(crate) struct FineDuration {
     pub picos: u128,
 }
 
+/// Floors to nanosecond granularity, since `Duration` cannot represent
+/// picoseconds. Timers that measure finer than nanoseconds should instead use
+/// [`FineDuration::from_picos`].
 impl From<Duration> for FineDuration {
     #[inline]
     fn from(duration: Duration) -> Self {
@@ -21,6 +24,21 @@ impl From<Duration> for FineDuration {
     }
 }
 
+/// Truncates picoseconds to nanoseconds, since `Duration` cannot represent
+/// them.
+///
+/// Fails if the number of seconds does not fit in `u64`.
+impl TryFrom<FineDuration> for Duration {
+    type Error = TryFromIntError;
+
+    #[inline]
+    fn try_from(duration: FineDuration) -> Result<Self, Self::Error> {
+        let secs = u64::try_from(duration.picos / picos::SEC)?;
+        let nanos = ((duration.picos % picos::SEC) / picos::NANOS) as u32;
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
 impl fmt::Display for FineDuration {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let sig_figs = f.precision().unwrap_or(4);
@@ -107,11 +125,32 @@ impl<I: Into<u128>> ops::Div<I> for FineDuration {
 impl FineDuration {
     pub const MAX: Self = Self { picos: u128::MAX };
 
+    /// Creates a duration from picoseconds, without losing sub-nanosecond
+    /// precision like conversion from `Duration`.
+    #[inline]
+    pub const fn from_picos(picos: u128) -> Self {
+        Self { picos }
+    }
+
     #[inline]
     pub fn is_zero(&self) -> bool {
         self.picos == 0
     }
 
+    /// Converts to `Duration` by truncating picoseconds to nanoseconds and
+    /// saturating at [`Duration::MAX`].
+    #[inline]
+    pub fn as_duration_lossy(self) -> Duration {
+        Duration::try_from(self).unwrap_or(Duration::MAX)
+    }
+
+    /// Returns the number of seconds, which may lose precision for very large
+    /// values.
+    #[inline]
+    pub fn as_secs_f64(self) -> f64 {
+        self.picos as f64 / picos::SEC as f64
+    }
+
     /// Round up to `other` if `self` is zero.
     #[inline]
     pub fn clamp_to(self, other: Self) -> Self {
@@ -228,6 +267,54 @@ impl TimeScale {
 mod tests {
     use super::*;
 
+    #[test]
+    fn into_duration() {
+        #[track_caller]
+        fn test(picos: u128, expected: Option<Duration>) {
+            let duration = FineDuration { picos };
+            assert_eq!(Duration::try_from(duration).ok(), expected);
+            assert_eq!(duration.as_duration_lossy(), expected.unwrap_or(Duration::MAX));
+        }
+
+        test(0, Some(Duration::ZERO));
+
+        // Picoseconds are truncated.
+        test(999, Some(Duration::ZERO));
+        test(1_000, Some(Duration::from_nanos(1)));
+        test(1_999, Some(Duration::from_nanos(1)));
+        test(picos::SEC - 1, Some(Duration::new(0, 999_999_999)));
+        test(picos::SEC, Some(Duration::from_secs(1)));
+        test(picos::SEC + 1_000, Some(Duration::new(1, 1)));
+
+        // Round-trips through `FineDuration::from`.
+        let max_fine = Duration::new(u64::MAX, 999_999_999);
+        test(FineDuration::from(max_fine).picos, Some(max_fine));
+
+        // Out of `Duration` range.
+        test((u64::MAX as u128 + 1) * picos::SEC, None);
+        test(u128::MAX, None);
+    }
+
+    #[test]
+    fn sub_nanos() {
+        // Sub-nanosecond precision is kept when constructed from picoseconds,
+        // including per-iteration division.
+        assert_eq!(FineDuration::from_picos(1_500).picos, 1_500);
+        assert_eq!((FineDuration::from_picos(1_000) / 3u32).picos, 333);
+        assert_eq!(FineDuration::from_picos(333).to_string(), "0.333 ns");
+
+        // `Duration` cannot represent picoseconds.
+        assert_eq!(FineDuration::from(Duration::from_nanos(1)), FineDuration::from_picos(1_000));
+    }
+
+    #[test]
+    fn as_secs_f64() {
+        assert_eq!(FineDuration { picos: 0 }.as_secs_f64(), 0.0);
+        assert_eq!(FineDuration { picos: picos::SEC }.as_secs_f64(), 1.0);
+        assert_eq!(FineDuration { picos: picos::SEC / 2 }.as_secs_f64(), 0.5);
+        assert_eq!(FineDuration { picos: 1 }.as_secs_f64(), 1e-12);
+    }
+
     #[test]
     fn clamp_to() {
         #[track_caller]
@@ -130,8 +130,6 @@ impl Timer {
 
     /// Calculates the per-timing-sample benchmarking loop overhead.
     pub fn measure_sample_loop_overhead(self) -> FineDuration {
-        let timer_kind = self.kind();
-
         let sample_count: usize = 100;
         let sample_size: usize = 10_000;
 
@@ -139,26 +137,49 @@ impl Timer {
         let mut min_sample = FineDuration::default();
 
         for _ in 0..sample_count {
-            let start = UntaggedTimestamp::start(timer_kind);
+            let mut sample = self.time_empty_loop(sample_size);
+            sample.picos /= sample_size as u128;
 
-            for i in 0..sample_size {
-                _ = crate::black_box(i);
-            }
+            min_sample = min_sample.clamp_to_min(sample);
+        }
 
-            let end = UntaggedTimestamp::end(timer_kind);
+        min_sample
+    }
 
-            // SAFETY: These values are guaranteed to be the correct variant because
-            // they were created from the same `timer_kind`.
-            let [start, end] =
-                unsafe { [start.into_timestamp(timer_kind), end.into_timestamp(timer_kind)] };
+    /// Calculates the relative standard deviation of timing a fixed workload,
+    /// as a percentage.
+    ///
+    /// The workload always does the same work, so variation between samples
+    /// comes from the machine, such as from other processes or frequency
+    /// scaling.
+    pub fn measure_noise(self) -> f64 {
+        let sample_count: usize = 50;
+        let sample_size: usize = 100_000;
 
-            let mut sample = end.duration_since(start, self);
-            sample.picos /= sample_size as u128;
+        let samples: Vec<FineDuration> =
+            (0..sample_count).map(|_| self.time_empty_loop(sample_size)).collect();
 
-            min_sample = min_sample.clamp_to_min(sample);
+        crate::stats::relative_std_dev(&samples)
+    }
+
+    /// Times `iter_count` iterations of an empty loop.
+    fn time_empty_loop(self, iter_count: usize) -> FineDuration {
+        let timer_kind = self.kind();
+
+        let start = UntaggedTimestamp::start(timer_kind);
+
+        for i in 0..iter_count {
+            _ = crate::black_box(i);
         }
 
-        min_sample
+        let end = UntaggedTimestamp::end(timer_kind);
+
+        // SAFETY: These values are guaranteed to be the correct variant because
+        // they were created from the same `timer_kind`.
+        let [start, end] =
+            unsafe { [start.into_timestamp(timer_kind), end.into_timestamp(timer_kind)] };
+
+        end.duration_since(start, self)
     }
 }
 
@@ -72,7 +72,7 @@ impl TscTimestamp {
             return Default::default();
         };
 
-        FineDuration { picos: (diff as u128 * PICOS) / frequency.get() as u128 }
+        FineDuration::from_picos((diff as u128 * PICOS) / frequency.get() as u128)
     }
 }
 
@@ -110,3 +110,30 @@ impl fmt::Display for TscUnavailable {
         f.write_str(reason)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that timestamp differences keep sub-nanosecond precision.
+    #[test]
+    fn duration_since_sub_nanos() {
+        let frequency = NonZeroU64::new(3_000_000_000).unwrap();
+        let duration = |ticks: u64| {
+            TscTimestamp { value: 100 + ticks }
+                .duration_since(TscTimestamp { value: 100 }, frequency)
+                .picos
+        };
+
+        assert_eq!(duration(0), 0);
+        assert_eq!(duration(1), 333);
+        assert_eq!(duration(2), 666);
+        assert_eq!(duration(3), 1_000);
+        assert_eq!(duration(3_000_000_000), 1_000_000_000_000);
+
+        // Earlier timestamps saturate to 0.
+        let earlier =
+            TscTimestamp { value: 0 }.duration_since(TscTimestamp { value: 1 }, frequency);
+        assert_eq!(earlier.picos, 0);
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     alloc::{AllocOp, AllocTally},
     counter::{AnyCounter, BytesFormat, KnownCounterKind},
     stats::{Stats, StatsSet},
+    time::FineDuration,
     util,
 };
 
@@ -17,6 +18,9 @@ pub(crate) struct TreePainter {
     /// information should be left-padded to start at this column.
     max_name_span: usize,
 
+    /// Columns to show, in order. Empty if not showing columns.
+    columns: Vec<TreeColumn>,
+
     column_widths: [usize; TreeColumn::COUNT],
 
     depth: usize,
@@ -28,23 +32,108 @@ pub(crate) struct TreePainter {
 
     /// Buffer for writing to before printing to stdout.
     write_buf: String,
+
+    /// Names of the nodes leading to the current node.
+    path: Vec<String>,
+
+    /// Times below this are shown as `<{resolution}` rather than with
+    /// meaningless precision.
+    resolution: FineDuration,
+
+    /// Whether any time was shown as below `resolution`.
+    did_hit_resolution: bool,
+
+    /// Peak memory bandwidth in bytes per second, against which byte
+    /// throughput is shown as a percentage.
+    peak_bandwidth: Option<f64>,
+
+    /// Paths of benchmarks whose samples may be dominated by timer overhead.
+    overhead_bound_paths: Vec<String>,
+
+    /// Paths of benchmarks that reached `max_time` before collecting all
+    /// samples.
+    time_limited_paths: Vec<String>,
+
+    /// Paths of benchmarks that exceeded `max_allocs`, with their most
+    /// allocations per iteration and the limit.
+    max_allocs_exceeded: Vec<(String, u64, u64)>,
 }
 
 impl TreePainter {
-    pub fn new(max_name_span: usize, column_widths: [usize; TreeColumn::COUNT]) -> Self {
+    pub fn new(
+        max_name_span: usize,
+        columns: Vec<TreeColumn>,
+        column_widths: [usize; TreeColumn::COUNT],
+        resolution: FineDuration,
+        peak_bandwidth: Option<f64>,
+    ) -> Self {
         Self {
             max_name_span,
+            columns,
             column_widths,
             depth: 0,
             current_prefix: String::new(),
             write_buf: String::new(),
+            path: Vec::new(),
+            resolution,
+            did_hit_resolution: false,
+            peak_bandwidth,
+            overhead_bound_paths: Vec::new(),
+            time_limited_paths: Vec::new(),
+            max_allocs_exceeded: Vec::new(),
         }
     }
 }
 
 impl TreePainter {
+    /// Returns the names of the nodes leading to and including the current
+    /// node.
+    pub fn current_path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Returns `true` if any time was shown as below the resolution.
+    pub fn did_hit_resolution(&self) -> bool {
+        self.did_hit_resolution
+    }
+
+    /// Records the current node as a benchmark whose samples may be dominated
+    /// by timer overhead.
+    pub fn mark_overhead_bound(&mut self, path_separator: &str) {
+        self.overhead_bound_paths.push(self.path.join(path_separator));
+    }
+
+    /// Returns paths recorded by [`TreePainter::mark_overhead_bound`].
+    pub fn overhead_bound_paths(&self) -> &[String] {
+        &self.overhead_bound_paths
+    }
+
+    /// Records the current node as a benchmark that reached `max_time` before
+    /// collecting all samples.
+    pub fn mark_time_limited(&mut self, path_separator: &str) {
+        self.time_limited_paths.push(self.path.join(path_separator));
+    }
+
+    /// Returns paths recorded by [`TreePainter::mark_time_limited`].
+    pub fn time_limited_paths(&self) -> &[String] {
+        &self.time_limited_paths
+    }
+
+    /// Records the current node as a benchmark that made more than
+    /// `max_allocs` allocations in an iteration.
+    pub fn mark_max_allocs_exceeded(&mut self, path_separator: &str, allocs: u64, max_allocs: u64) {
+        self.max_allocs_exceeded.push((self.path.join(path_separator), allocs, max_allocs));
+    }
+
+    /// Returns benchmarks recorded by [`TreePainter::mark_max_allocs_exceeded`].
+    pub fn max_allocs_exceeded(&self) -> &[(String, u64, u64)] {
+        &self.max_allocs_exceeded
+    }
+
     /// Enter a parent node.
     pub fn start_parent(&mut self, name: &str, is_last: bool) {
+        self.path.push(name.to_owned());
+
         let is_top_level = self.depth == 0;
         let has_columns = self.has_columns();
 
@@ -75,12 +164,16 @@ impl TreePainter {
         // Write column headings.
         if has_columns && is_top_level {
             let names = TreeColumnData::from_fn(TreeColumn::name);
-            names.write(buf, &mut self.column_widths);
+            names.write(buf, &self.columns, &mut self.column_widths);
         }
 
         // Write column spacers.
         if has_columns && !is_top_level {
-            TreeColumnData([""; TreeColumn::COUNT]).write(buf, &mut self.column_widths);
+            TreeColumnData([""; TreeColumn::COUNT]).write(
+                buf,
+                &self.columns,
+                &mut self.column_widths,
+            );
         }
 
         println!("{buf}");
@@ -92,8 +185,20 @@ impl TreePainter {
         }
     }
 
+    /// Enter a parent node that is hidden by `--strip-prefix`, so that its
+    /// children are painted in its place.
+    pub fn start_hidden_parent(&mut self, name: &str) {
+        self.path.push(name.to_owned());
+    }
+
+    /// Exit the current hidden parent node.
+    pub fn finish_hidden_parent(&mut self) {
+        self.path.pop();
+    }
+
     /// Exit the current parent node.
     pub fn finish_parent(&mut self) {
+        self.path.pop();
         self.depth -= 1;
 
         // Improve legibility for multiple top-level parents.
@@ -115,6 +220,7 @@ impl TreePainter {
     /// This semantically combines start/finish operations.
     pub fn ignore_leaf(&mut self, name: &str, is_last: bool) {
         let has_columns = self.has_columns();
+        let first_column = self.first_column();
 
         let buf = &mut self.write_buf;
         buf.clear();
@@ -135,7 +241,11 @@ impl TreePainter {
         }
 
         if has_columns {
-            TreeColumnData::from_first("(ignored)").write(buf, &mut self.column_widths);
+            TreeColumnData::from_first(first_column, "(ignored)").write(
+                buf,
+                &self.columns,
+                &mut self.column_widths,
+            );
         } else {
             buf.push_str("(ignored)");
         }
@@ -145,6 +255,8 @@ impl TreePainter {
 
     /// Enter a leaf node.
     pub fn start_leaf(&mut self, name: &str, is_last: bool) {
+        self.path.push(name.to_owned());
+
         let has_columns = self.has_columns();
 
         let buf = &mut self.write_buf;
@@ -171,14 +283,57 @@ impl TreePainter {
 
     /// Exit the current leaf node.
     pub fn finish_empty_leaf(&mut self) {
+        self.path.pop();
         println!();
     }
 
+    /// Exit the current leaf node, indicating why it has no statistics.
+    pub fn finish_skipped_leaf(&mut self, label: &str) {
+        self.path.pop();
+
+        let has_columns = self.has_columns();
+        let first_column = self.first_column();
+
+        let buf = &mut self.write_buf;
+        buf.clear();
+
+        if has_columns {
+            TreeColumnData::from_first(first_column, label).write(
+                buf,
+                &self.columns,
+                &mut self.column_widths,
+            );
+        } else {
+            buf.extend([" ", label]);
+        }
+
+        println!("{buf}");
+    }
+
     /// Exit the current leaf node, emitting statistics.
     pub fn finish_leaf(&mut self, is_last: bool, stats: &Stats, bytes_format: BytesFormat) {
+        self.path.pop();
+
+        let first_column = self.first_column();
+
+        // Setup and allocation rows only have values under time stats.
+        let has_time_stats = self.columns.iter().any(|column| column.is_time_stat());
+
         let buf = &mut self.write_buf;
         buf.clear();
 
+        // Formats times below the resolution as an upper bound.
+        let resolution = self.resolution;
+        let mut did_hit_resolution = false;
+        let mut format_time = |time: FineDuration| -> String {
+            if time < resolution {
+                did_hit_resolution = true;
+                format!("<{resolution}")
+            } else {
+                time.to_string()
+            }
+        };
+
         // Serialize alloc stats early so we can resize columns early.
         let serialized_alloc_tallies = AllocOp::ALL.map(|op| {
             let tally = stats.alloc_tallies.get(op);
@@ -188,7 +343,7 @@ impl TreePainter {
             }
 
             let column_tallies = TreeColumn::ALL.map(|column| {
-                let prefix = if column.is_first() { "  " } else { "" };
+                let prefix = if column == first_column { "  " } else { "" };
 
                 let tally = AllocTally {
                     count: column.get_stat(&tally.count).copied()?,
@@ -234,10 +389,59 @@ impl TreePainter {
                 .map(Option::unwrap_or_default)
         });
 
+        // Serialize byte throughput as a percentage of peak bandwidth.
+        let serialized_peak_bandwidth = self.peak_bandwidth.and_then(|peak_bandwidth| {
+            let bytes_stats = stats.get_counts(KnownCounterKind::Bytes)?;
+
+            Some(TreeColumn::ALL.map(|column| -> String {
+                let (Some(&bytes), Some(time)) =
+                    (column.get_stat(bytes_stats), column.get_stat(&stats.time))
+                else {
+                    return String::new();
+                };
+
+                // Times can be zero after subtracting sample loop overhead.
+                if time.picos == 0 {
+                    return String::new();
+                }
+
+                let secs = time.picos as f64 / 1e12;
+                let percent = bytes as f64 / secs / peak_bandwidth * 100.0;
+
+                format!("{}% of peak", util::fmt::format_f64(percent, 3))
+            }))
+        });
+
+        // Serialize setup and teardown time stats early so we can resize
+        // columns early.
+        let serialized_phase_times =
+            [("setup:", &stats.setup_time), ("teardown:", &stats.teardown_time)].map(
+                |(label, phase_time)| {
+                    let phase_time = phase_time.as_ref()?;
+
+                    Some((
+                        label,
+                        TreeColumn::ALL.map(|column| {
+                            let prefix = if column == first_column { "  " } else { "" };
+
+                            column
+                                .get_stat(phase_time)
+                                .map(|&time| format!("{prefix}{}", format_time(time)))
+                                .unwrap_or_default()
+                        }),
+                    ))
+                },
+            );
+
         for column in TreeColumn::time_stats() {
             let width = &mut self.column_widths[column as usize];
 
-            for counter in &serialized_counters {
+            for (_, phase_time) in serialized_phase_times.iter().flatten() {
+                let s = &phase_time[column as usize];
+                *width = (*width).max(s.chars().count());
+            }
+
+            for counter in serialized_counters.iter().chain(&serialized_peak_bandwidth) {
                 let s = &counter[column as usize];
                 *width = (*width).max(s.chars().count());
             }
@@ -254,28 +458,45 @@ impl TreePainter {
 
         // Write time stats with iter and sample counts.
         TreeColumnData::from_fn(|column| -> String {
-            let stat: &dyn ToString = match column {
-                TreeColumn::Fastest => &stats.time.fastest,
-                TreeColumn::Slowest => &stats.time.slowest,
-                TreeColumn::Median => &stats.time.median,
-                TreeColumn::Mean => &stats.time.mean,
-                TreeColumn::Samples => &stats.sample_count,
-                TreeColumn::Iters => &stats.iter_count,
-            };
-            stat.to_string()
+            match column {
+                TreeColumn::Fastest => format_time(stats.time.fastest),
+                TreeColumn::Slowest => format_time(stats.time.slowest),
+                TreeColumn::Median => format_time(stats.time.median),
+                TreeColumn::Mean => format_time(stats.time.mean),
+                TreeColumn::Samples => stats.sample_count.to_string(),
+                TreeColumn::Iters => stats.iter_count.to_string(),
+                TreeColumn::Iqr => format_time(stats.iqr_time),
+                TreeColumn::Mad => format_time(stats.mad_time),
+                TreeColumn::StdDev => format_time(stats.std_dev_time),
+                TreeColumn::P99 => format_time(stats.p99_time),
+                TreeColumn::P999 => format_time(stats.p999_time),
+                TreeColumn::Cold => stats.cold_time.map(&mut format_time).unwrap_or_default(),
+            }
         })
         .as_ref::<str>()
-        .write(buf, &mut self.column_widths);
+        .write(buf, &self.columns, &mut self.column_widths);
+
+        self.did_hit_resolution |= did_hit_resolution;
 
         println!("{buf}");
 
-        // Write counter stats.
-        let counter_stats = serialized_counters.map(TreeColumnData);
-        for counter_kind in KnownCounterKind::ALL {
-            let counter_stats = counter_stats[counter_kind as usize].as_ref::<str>();
+        // Write counter stats, with the percentage of peak bandwidth following
+        // bytes.
+        let [bytes_stats, chars_stats, items_stats] = serialized_counters.map(TreeColumnData);
+        let peak_bandwidth_stats = serialized_peak_bandwidth.map(TreeColumnData);
+        for counter_stats in [
+            Some(&bytes_stats),
+            peak_bandwidth_stats.as_ref(),
+            Some(&chars_stats),
+            Some(&items_stats),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let counter_stats = counter_stats.as_ref::<str>();
 
             // Skip empty rows.
-            if counter_stats.0.iter().all(|s| s.is_empty()) {
+            if self.columns.iter().all(|&column| counter_stats.0[column as usize].is_empty()) {
                 continue;
             }
 
@@ -298,13 +519,46 @@ impl TreePainter {
                 }
             };
 
-            counter_stats.write(buf, &mut self.column_widths);
+            counter_stats.write(buf, &self.columns, &mut self.column_widths);
             println!("{buf}");
         }
 
+        // Write time spent generating inputs and releasing outputs.
+        for (label, phase_time) in
+            serialized_phase_times.iter().flatten().filter(|_| has_time_stats)
+        {
+            let values = TreeColumnData::from_fn(|column| phase_time[column as usize].as_str());
+
+            for row in [TreeColumnData::from_first(first_column, *label), values] {
+                buf.clear();
+                buf.push_str(&self.current_prefix);
+
+                if !is_last {
+                    buf.push('│');
+                }
+
+                // Right-pad buffer.
+                {
+                    let buf_len = buf.chars().count();
+                    let max_span = self.max_name_span;
+                    let pad_len = TREE_COL_BUF + self.max_name_span.saturating_sub(buf_len);
+                    buf.extend(repeat(' ').take(pad_len));
+
+                    if buf_len > max_span {
+                        self.max_name_span = buf_len;
+                    }
+                };
+
+                row.write(buf, &self.columns, &mut self.column_widths);
+                println!("{buf}");
+            }
+        }
+
         // Write allocation information.
         for op in [AllocOp::Alloc, AllocOp::Dealloc, AllocOp::Grow, AllocOp::Shrink] {
-            let Some(tallies) = &serialized_alloc_tallies[op as usize] else {
+            let Some(tallies) =
+                serialized_alloc_tallies[op as usize].as_ref().filter(|_| has_time_stats)
+            else {
                 continue;
             };
 
@@ -327,7 +581,11 @@ impl TreePainter {
                 }
             };
 
-            TreeColumnData::from_first(op.prefix()).write(buf, &mut self.column_widths);
+            TreeColumnData::from_first(first_column, op.prefix()).write(
+                buf,
+                &self.columns,
+                &mut self.column_widths,
+            );
             println!("{buf}");
 
             for value in tallies.as_array() {
@@ -350,8 +608,11 @@ impl TreePainter {
                     }
                 };
 
-                TreeColumnData::from_fn(|column| value[column as usize].as_str())
-                    .write(buf, &mut self.column_widths);
+                TreeColumnData::from_fn(|column| value[column as usize].as_str()).write(
+                    buf,
+                    &self.columns,
+                    &mut self.column_widths,
+                );
 
                 println!("{buf}");
             }
@@ -359,7 +620,12 @@ impl TreePainter {
     }
 
     fn has_columns(&self) -> bool {
-        !self.column_widths.iter().all(|&w| w == 0)
+        !self.columns.is_empty()
+    }
+
+    /// Returns the leftmost column, under which row labels are written.
+    fn first_column(&self) -> TreeColumn {
+        self.columns.first().copied().unwrap_or(TreeColumn::Fastest)
     }
 }
 
@@ -372,12 +638,24 @@ pub(crate) enum TreeColumn {
     Mean,
     Samples,
     Iters,
+    Iqr,
+    Mad,
+    StdDev,
+    P99,
+    P999,
+    Cold,
 }
 
 impl TreeColumn {
-    pub const COUNT: usize = 6;
+    pub const COUNT: usize = 12;
 
     pub const ALL: [Self; Self::COUNT] = {
+        use TreeColumn::*;
+        [Fastest, Slowest, Median, Mean, Samples, Iters, Iqr, Mad, StdDev, P99, P999, Cold]
+    };
+
+    /// Columns shown if not set via `--columns`.
+    pub const DEFAULT: [Self; 6] = {
         use TreeColumn::*;
         [Fastest, Slowest, Median, Mean, Samples, Iters]
     };
@@ -388,19 +666,7 @@ impl TreeColumn {
         [Fastest, Slowest, Median, Mean].into_iter()
     }
 
-    #[inline]
-    pub fn is_first(self) -> bool {
-        let [first, ..] = Self::ALL;
-        self == first
-    }
-
-    #[inline]
-    pub fn is_last(self) -> bool {
-        let [.., last] = Self::ALL;
-        self == last
-    }
-
-    fn name(self) -> &'static str {
+    pub fn name(self) -> &'static str {
         match self {
             Self::Fastest => "fastest",
             Self::Slowest => "slowest",
@@ -408,13 +674,34 @@ impl TreeColumn {
             Self::Mean => "mean",
             Self::Samples => "samples",
             Self::Iters => "iters",
+            Self::Iqr => "iqr",
+            Self::Mad => "mad",
+            Self::StdDev => "stddev",
+            Self::P99 => "p99",
+            Self::P999 => "p99.9",
+            Self::Cold => "cold",
         }
     }
 
+    /// Parses a `--columns` name, including the `min` and `max` aliases of
+    /// `fastest` and `slowest`.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "min" => return Ok(Self::Fastest),
+            "max" => return Ok(Self::Slowest),
+            _ => {}
+        }
+
+        Self::ALL.into_iter().find(|column| column.name() == name).ok_or_else(|| {
+            let valid = Self::ALL.map(Self::name).join(", ");
+            format!("unknown column '{name}' (valid: {valid})")
+        })
+    }
+
     #[inline]
     pub fn is_time_stat(self) -> bool {
         use TreeColumn::*;
-        matches!(self, Fastest | Slowest | Median | Mean)
+        matches!(self, Fastest | Slowest | Median | Mean | Iqr | Mad | StdDev | P99 | P999 | Cold)
     }
 
     #[inline]
@@ -424,7 +711,14 @@ impl TreeColumn {
             Self::Slowest => Some(&stats.slowest),
             Self::Median => Some(&stats.median),
             Self::Mean => Some(&stats.mean),
-            Self::Samples | Self::Iters => None,
+            Self::Samples
+            | Self::Iters
+            | Self::Iqr
+            | Self::Mad
+            | Self::StdDev
+            | Self::P99
+            | Self::P999
+            | Self::Cold => None,
         }
     }
 }
@@ -434,12 +728,12 @@ struct TreeColumnData<T>([T; TreeColumn::COUNT]);
 
 impl<T> TreeColumnData<T> {
     #[inline]
-    fn from_first(value: T) -> Self
+    fn from_first(column: TreeColumn, value: T) -> Self
     where
         Self: Default,
     {
         let mut data = Self::default();
-        data.0[0] = value;
+        data.0[column as usize] = value;
         data
     }
 
@@ -453,12 +747,19 @@ impl<T> TreeColumnData<T> {
 }
 
 impl TreeColumnData<&str> {
-    /// Writes the column data into the buffer.
-    fn write(&self, buf: &mut String, column_widths: &mut [usize; TreeColumn::COUNT]) {
-        for (column, value) in self.0.iter().enumerate() {
-            let is_first = column == 0;
-            let is_last = column == TreeColumn::COUNT - 1;
-
+    /// Writes the data of `columns` into the buffer in order.
+    fn write(
+        &self,
+        buf: &mut String,
+        columns: &[TreeColumn],
+        column_widths: &mut [usize; TreeColumn::COUNT],
+    ) {
+        for (i, &column) in columns.iter().enumerate() {
+            let is_first = i == 0;
+            let is_last = i == columns.len() - 1;
+
+            let column = column as usize;
+            let value = self.0[column];
             let value_width = value.chars().count();
 
             // Write separator.
@@ -6,6 +6,7 @@ use std::{
 };
 
 pub mod fmt;
+pub mod rand;
 pub mod sync;
 
 /// Public-in-private type like `()` but meant to be externally-unreachable.
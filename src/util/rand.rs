@@ -0,0 +1,79 @@
+//! Seedable pseudorandom number generation.
+
+/// Small non-cryptographic PRNG based on [SplitMix64](https://prng.di.unimi.it/splitmix64.c).
+///
+/// This is used where reproducibility from a printed seed matters more than
+/// statistical quality.
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns a seed that differs between runs.
+    pub fn random_seed() -> u64 {
+        use std::{
+            collections::hash_map::RandomState,
+            hash::{BuildHasher, Hasher},
+            time::SystemTime,
+        };
+
+        let mut hasher = RandomState::new().build_hasher();
+
+        if let Ok(now) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            hasher.write_u128(now.as_nanos());
+        }
+        hasher.write_u32(std::process::id());
+
+        hasher.finish()
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..bound`.
+    ///
+    /// The slight modulo bias is negligible for the small bounds used here.
+    #[inline]
+    pub fn gen_index(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Randomly reorders `slice` via Fisher-Yates.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.gen_index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shuffle_is_reproducible() {
+        let shuffled = |seed: u64| {
+            let mut values: Vec<u32> = (0..100).collect();
+            Rng::new(seed).shuffle(&mut values);
+            values
+        };
+
+        assert_eq!(shuffled(42), shuffled(42));
+        assert_ne!(shuffled(42), shuffled(43));
+
+        let mut sorted = shuffled(42);
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..100).collect::<Vec<u32>>());
+    }
+}
@@ -5,11 +5,15 @@
 
 use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
 
-use divan::Divan;
+use divan::{Bencher, Divan};
 
 static CHILD1_ITERS: AtomicUsize = AtomicUsize::new(0);
 static CHILD2_ITERS: AtomicUsize = AtomicUsize::new(0);
 static CHILD3_ITERS: AtomicUsize = AtomicUsize::new(0);
+static PANIC_ITERS: AtomicUsize = AtomicUsize::new(0);
+static BENCHER_ARGS_SUM: AtomicUsize = AtomicUsize::new(0);
+static LIVE_RETURNS: AtomicUsize = AtomicUsize::new(0);
+static MAX_LIVE_RETURNS: AtomicUsize = AtomicUsize::new(0);
 
 #[divan::bench_group(sample_count = 10, sample_size = 50)]
 mod parent {
@@ -48,6 +52,71 @@ mod parent {
     }
 }
 
+// 10 × 5 + 2 × 10 × 5 = 150
+mod panics {
+    use super::*;
+
+    #[divan::bench(should_panic, sample_count = 10, sample_size = 5)]
+    fn bench() {
+        PANIC_ITERS.fetch_add(1, SeqCst);
+        panic!("expected");
+    }
+
+    #[divan::bench(should_panic, sample_count = 10, sample_size = 5, args = [1, 2])]
+    fn bench_args(n: usize) -> usize {
+        PANIC_ITERS.fetch_add(1, SeqCst);
+        [0][n]
+    }
+}
+
+// (1 + 2) × 2 × 3 = 18
+// (1 + 2) × 2 × 3 × (1 + 2) = 54
+mod bencher_args {
+    use super::*;
+
+    #[divan::bench(sample_count = 2, sample_size = 3, args = [1, 2])]
+    fn bench(bencher: Bencher, n: usize) {
+        bencher.bench(|| BENCHER_ARGS_SUM.fetch_add(n, SeqCst));
+    }
+
+    #[divan::bench(types = [u8, u16], sample_count = 2, sample_size = 3, args = [1, 2])]
+    fn generic<T>(bencher: Bencher, n: usize) {
+        bencher.bench(|| BENCHER_ARGS_SUM.fetch_add(n * std::mem::size_of::<T>(), SeqCst));
+    }
+}
+
+// Returned values are dropped within each iteration rather than deferred, so at
+// most 1 is ever alive.
+mod unused_return {
+    use super::*;
+
+    struct Live;
+
+    impl Live {
+        fn new() -> Self {
+            let live = LIVE_RETURNS.fetch_add(1, SeqCst) + 1;
+            MAX_LIVE_RETURNS.fetch_max(live, SeqCst);
+            Live
+        }
+    }
+
+    impl Drop for Live {
+        fn drop(&mut self) {
+            LIVE_RETURNS.fetch_sub(1, SeqCst);
+        }
+    }
+
+    #[divan::bench(black_box_return = false, sample_count = 2, sample_size = 5)]
+    fn bench() -> Live {
+        Live::new()
+    }
+
+    #[divan::bench(black_box_return = false, sample_count = 2, sample_size = 5, args = [1, 2])]
+    fn bench_args(_: usize) -> Live {
+        Live::new()
+    }
+}
+
 #[test]
 fn iter_count() {
     Divan::default().run_benches();
@@ -55,4 +124,7 @@ fn iter_count() {
     assert_eq!(CHILD1_ITERS.load(SeqCst), 10);
     assert_eq!(CHILD2_ITERS.load(SeqCst), 2100);
     assert_eq!(CHILD3_ITERS.load(SeqCst), 50);
+    assert_eq!(PANIC_ITERS.load(SeqCst), 150);
+    assert_eq!(BENCHER_ARGS_SUM.load(SeqCst), 18 + 54);
+    assert_eq!(MAX_LIVE_RETURNS.load(SeqCst), 1);
 }
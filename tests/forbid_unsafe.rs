@@ -42,6 +42,21 @@ fn contextual_generic_type_const_1<T, const N: usize>(_: Bencher) {}
 #[divan::bench(types = [i32, &str], consts = CONST_VALUES)]
 fn contextual_generic_type_const_2<T, const N: usize>(_: Bencher) {}
 
+#[divan::bench(args = [1, 5, 10], black_box_args)]
+fn freestanding_black_box_args(_: usize) {}
+
+#[divan::bench(args = [1, 5, 10], black_box_args)]
+fn contextual_black_box_args(_: Bencher, _: usize) {}
+
+#[divan::bench(args = [1, 5, 10], args_as_items, args_as_bytes = false)]
+fn freestanding_args_as_items(_: usize) {}
+
+#[divan::bench(args = [1u8, 5, 10], args_as_bytes, args_as_chars)]
+fn contextual_args_as_bytes(_: Bencher, _: &u8) {}
+
+#[divan::bench(black_box_args = false, args_as_items = false)]
+fn freestanding_without_args() {}
+
 #[divan::bench_group]
 mod group {
     use super::*;
@@ -0,0 +1,26 @@
+// Tests that hooks are called once around the entire run.
+
+// Miri cannot discover benchmarks.
+#![cfg(not(miri))]
+
+use std::sync::Mutex;
+
+use divan::Divan;
+
+static EVENTS: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+#[divan::bench]
+fn bench() {
+    EVENTS.lock().unwrap().push("bench");
+}
+
+#[test]
+fn order() {
+    Divan::default()
+        .before_all(|| EVENTS.lock().unwrap().push("before 1"))
+        .before_all(|| EVENTS.lock().unwrap().push("before 2"))
+        .after_all(|| EVENTS.lock().unwrap().push("after"))
+        .test_benches();
+
+    assert_eq!(*EVENTS.lock().unwrap(), ["before 1", "before 2", "bench", "after"]);
+}
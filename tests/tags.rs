@@ -0,0 +1,53 @@
+// Tests that benchmarks are selected by tags.
+
+// Miri cannot discover benchmarks.
+#![cfg(not(miri))]
+
+use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+use divan::Divan;
+
+static SIMD_RUNS: AtomicUsize = AtomicUsize::new(0);
+static SIMD_HOT_RUNS: AtomicUsize = AtomicUsize::new(0);
+static UNTAGGED_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+#[divan::bench_group(tags = ["simd"])]
+mod simd {
+    use super::*;
+
+    #[divan::bench]
+    fn bench() {
+        SIMD_RUNS.fetch_add(1, SeqCst);
+    }
+
+    // Inherits "simd" from the parent group.
+    #[divan::bench(tags = ["hot"])]
+    fn hot() {
+        SIMD_HOT_RUNS.fetch_add(1, SeqCst);
+    }
+}
+
+#[divan::bench]
+fn untagged() {
+    UNTAGGED_RUNS.fetch_add(1, SeqCst);
+}
+
+#[test]
+fn tags() {
+    let runs = || [&SIMD_RUNS, &SIMD_HOT_RUNS, &UNTAGGED_RUNS].map(|runs| runs.swap(0, SeqCst));
+
+    Divan::default().tag("simd").test_benches();
+    assert_eq!(runs(), [1, 1, 0]);
+
+    Divan::default().tag("hot").tag("missing").test_benches();
+    assert_eq!(runs(), [0, 1, 0]);
+
+    Divan::default().tag("simd").tag("hot").match_all_tags().test_benches();
+    assert_eq!(runs(), [0, 1, 0]);
+
+    Divan::default().tag("hot").tag("missing").match_all_tags().test_benches();
+    assert_eq!(runs(), [0, 0, 0]);
+
+    Divan::default().test_benches();
+    assert_eq!(runs(), [1, 1, 1]);
+}
@@ -99,7 +99,8 @@ fn empty_types_consts_4<const C: usize, T>() {}
 
 #[test]
 fn test_fn() {
-    Divan::default().test_benches();
+    // Both `inner` functions have the path `weird_usage::inner`.
+    Divan::default().allow_duplicate_names().test_benches();
 }
 
 // Test that each function appears the expected number of times.